@@ -0,0 +1,37 @@
+//! Builds a tree directly from node types, without a definition, and counts how often it runs.
+//!
+//! Run with `cargo run -p unpowered --example counter`.
+
+use unpowered::*;
+
+#[derive(Clone)]
+struct Count;
+
+impl UnpoweredFunction for Count {
+    type Model = u32;
+    type Controller = Vec<u32>;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        if controller.len() as u32 >= *model {
+            return UnpoweredFunctionState::Failed;
+        }
+        controller.push(controller.len() as u32);
+        UnpoweredFunctionState::Complete
+    }
+
+    fn reset(self: &mut Self, _model: &Self::Model) {}
+}
+
+fn main() {
+    // Counts three at a time until the limit in the model is reached, then reports success.
+    let mut tree = Succeeder::new(Box::new(RepeatUntilFail::new(Box::new(Repeat::new(
+        Box::new(Count),
+        3,
+    )))));
+    let mut counted = vec![];
+    let state = tree.resume_with(&7, &mut counted);
+    println!("{:?} after counting {:?}", state, counted);
+}
@@ -0,0 +1,102 @@
+//! Loads a guard's behavior from RON and runs it for a few turns, printing each trace.
+//!
+//! Run with `cargo run -p unpowered --example guard`.
+
+use serde::Deserialize;
+use unpowered::*;
+
+const GUARD: &str = r#"
+Selector([
+    Sequence([
+        User(IntruderWithin(2)),
+        WindUp(1, User(Shout("Halt!"))),
+    ]),
+    Sequence([
+        User(IntruderWithin(5)),
+        User(Shout("Who goes there?")),
+    ]),
+    EveryNTurns(3, User(Shout("All quiet."))),
+])
+"#;
+
+struct Yard {
+    intruder_distance: Option<u32>,
+}
+
+#[derive(Default)]
+struct Guard {
+    turn: usize,
+    trace: Option<UnpoweredTrace>,
+}
+
+// The guard's tree never picks at random.
+impl UnpoweredRandom for Guard {}
+
+impl UnpoweredClock for Guard {
+    fn current_turn(self: &Self) -> usize {
+        self.turn
+    }
+}
+
+impl UnpoweredTracer for Guard {
+    fn trace_mut(self: &mut Self) -> Option<&mut UnpoweredTrace> {
+        self.trace.as_mut()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::reflect::TypePath))]
+enum GuardNode {
+    IntruderWithin(u32),
+    Shout(String),
+}
+
+impl UnpoweredFunction for GuardNode {
+    type Model = Yard;
+    type Controller = Guard;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        _controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        match self {
+            GuardNode::IntruderWithin(range) => match model.intruder_distance {
+                Some(distance) if distance <= *range => UnpoweredFunctionState::Complete,
+                _ => UnpoweredFunctionState::Failed,
+            },
+            GuardNode::Shout(line) => {
+                println!("Guard: {}", line);
+                UnpoweredFunctionState::Complete
+            }
+        }
+    }
+
+    fn reset(self: &mut Self, _model: &Self::Model) {}
+}
+
+fn main() {
+    let def: UnpoweredTreeDef<GuardNode, NoWrapper> =
+        ron::from_str(GUARD).expect("guard tree parses");
+    let mut tree = def.create_tree().expect("guard tree has no includes");
+    let mut guard = Guard::default();
+    for (turn, intruder_distance) in [None, Some(4), Some(2), Some(1), None, None]
+        .into_iter()
+        .enumerate()
+    {
+        guard.turn = turn;
+        guard.trace = Some(UnpoweredTrace::default());
+        println!("Turn {} (intruder at {:?})", turn, intruder_distance);
+        let state = tree.resume_with(&Yard { intruder_distance }, &mut guard);
+        if state != UnpoweredFunctionState::Waiting {
+            tree.reset(&Yard { intruder_distance });
+        }
+        for entry in guard.trace.take().unwrap().entries {
+            println!(
+                "  {}{} -> {:?}",
+                "  ".repeat(entry.depth),
+                entry.label,
+                entry.state
+            );
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use super::UnpoweredTrace;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum UnpoweredFunctionState {
+    Waiting,
+    // The powered function failed to complete all work (bad state or negative result).
+    Failed,
+    // The powered function completed all work.
+    Complete,
+}
+
+pub trait UnpoweredFunction {
+    type Model: 'static;
+    type Controller: 'static;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState;
+    fn reset(self: &mut Self, model: &Self::Model);
+}
+
+pub trait UtilityScorer {
+    type Model: 'static;
+    type Controller: 'static;
+    fn score(self: &Self, model: &Self::Model, controller: &Self::Controller) -> f32;
+}
+
+// The defaults below let a controller opt out with an empty impl when its trees don't need it.
+pub trait UnpoweredRandom {
+    // A value in 0..range, where range is never zero. Without randomness, always the first.
+    fn random_below(self: &mut Self, _range: u32) -> u32 {
+        0
+    }
+}
+
+pub trait UnpoweredClock {
+    // Stays the same for every node resumed during one turn. Without a clock it is always
+    // turn 0, so cooldowns never run out.
+    fn current_turn(self: &Self) -> usize {
+        0
+    }
+}
+
+pub trait UnpoweredTracer {
+    // None when this run is not being traced.
+    fn trace_mut(self: &mut Self) -> Option<&mut UnpoweredTrace> {
+        None
+    }
+}
@@ -0,0 +1,11 @@
+//! Behavior trees that run until a node waits, fails or completes, with no gas budget.
+//! Trees are built from serializable UnpoweredTreeDef values, with user-defined leaves.
+
+mod funcs;
+mod nodes;
+mod trace;
+mod tree_def;
+pub use funcs::*;
+pub use nodes::*;
+pub use trace::*;
+pub use tree_def::*;
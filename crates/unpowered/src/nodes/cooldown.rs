@@ -0,0 +1,206 @@
+use crate::*;
+
+pub struct Cooldown<M, C> {
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    turns: usize,
+    last_completed: Option<usize>,
+}
+
+impl<M, C> Cooldown<M, C> {
+    pub fn new(
+        turns: usize,
+        node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    ) -> Self {
+        Cooldown {
+            node,
+            turns,
+            last_completed: None,
+        }
+    }
+}
+
+impl<M: 'static, C: UnpoweredClock + 'static> UnpoweredFunction for Cooldown<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        let turn = controller.current_turn();
+        if let Some(last_completed) = self.last_completed {
+            if turn < last_completed + self.turns {
+                return UnpoweredFunctionState::Failed;
+            }
+        }
+        let result = self.node.resume_with(model, controller);
+        if result == UnpoweredFunctionState::Complete {
+            self.last_completed = Some(turn);
+        }
+        result
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        // The cooldown itself outlives resets.
+        self.node.reset(model);
+    }
+}
+
+pub struct Once<M, C> {
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    done: bool,
+}
+
+impl<M, C> Once<M, C> {
+    pub fn new(node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>) -> Self {
+        Once { node, done: false }
+    }
+}
+
+impl<M: 'static, C: 'static> UnpoweredFunction for Once<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        if self.done {
+            return UnpoweredFunctionState::Failed;
+        }
+        let result = self.node.resume_with(model, controller);
+        if result == UnpoweredFunctionState::Complete {
+            self.done = true;
+        }
+        result
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.node.reset(model);
+    }
+}
+
+pub struct EveryNTurns<M, C> {
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    turns: usize,
+    waiting: bool,
+}
+
+impl<M, C> EveryNTurns<M, C> {
+    pub fn new(
+        turns: usize,
+        node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    ) -> Self {
+        EveryNTurns {
+            node,
+            turns: turns.max(1),
+            waiting: false,
+        }
+    }
+}
+
+impl<M: 'static, C: UnpoweredClock + 'static> UnpoweredFunction for EveryNTurns<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        // A child that started waiting on its turn gets to finish off-turn.
+        if !self.waiting && controller.current_turn() % self.turns != 0 {
+            return UnpoweredFunctionState::Failed;
+        }
+        let result = self.node.resume_with(model, controller);
+        self.waiting = result == UnpoweredFunctionState::Waiting;
+        result
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.waiting = false;
+        self.node.reset(model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Turns {
+        turn: usize,
+        ran: Vec<usize>,
+    }
+
+    impl UnpoweredClock for Turns {
+        fn current_turn(self: &Self) -> usize {
+            self.turn
+        }
+    }
+
+    struct Record(UnpoweredFunctionState);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Turns;
+        fn resume_with(
+            self: &mut Self,
+            _model: &(),
+            controller: &mut Turns,
+        ) -> UnpoweredFunctionState {
+            controller.ran.push(controller.turn);
+            self.0
+        }
+
+        fn reset(self: &mut Self, _model: &()) {}
+    }
+
+    fn run_turns(node: &mut dyn UnpoweredFunction<Model = (), Controller = Turns>) -> Vec<usize> {
+        let mut controller = Turns::default();
+        for turn in 0..7 {
+            controller.turn = turn;
+            node.resume_with(&(), &mut controller);
+        }
+        controller.ran
+    }
+
+    #[test]
+    fn cooldown_waits_after_completing() {
+        let mut node = Cooldown::new(3, Box::new(Record(UnpoweredFunctionState::Complete)));
+        assert_eq!(run_turns(&mut node), vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn cooldown_ignores_failures() {
+        let mut node = Cooldown::new(3, Box::new(Record(UnpoweredFunctionState::Failed)));
+        assert_eq!(run_turns(&mut node), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn once_runs_until_first_completion() {
+        let mut node = Once::new(Box::new(Record(UnpoweredFunctionState::Complete)));
+        assert_eq!(run_turns(&mut node), vec![0]);
+        let mut node = Once::new(Box::new(Record(UnpoweredFunctionState::Failed)));
+        assert_eq!(run_turns(&mut node).len(), 7);
+    }
+
+    #[test]
+    fn every_n_turns_skips_other_turns() {
+        let mut node = EveryNTurns::new(3, Box::new(Record(UnpoweredFunctionState::Complete)));
+        assert_eq!(run_turns(&mut node), vec![0, 3, 6]);
+        let mut controller = Turns {
+            turn: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            node.resume_with(&(), &mut controller),
+            UnpoweredFunctionState::Failed
+        );
+    }
+
+    #[test]
+    fn every_n_turns_finishes_waiting_child() {
+        let mut node = EveryNTurns::new(3, Box::new(Record(UnpoweredFunctionState::Waiting)));
+        assert_eq!(run_turns(&mut node), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::bt::*;
+use crate::*;
 
 pub struct Executor<M, C> {
     nodes: Vec<Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>>,
@@ -60,3 +60,61 @@ impl<M: 'static, C: 'static> UnpoweredFunction for Executor<M, C> {
         self.success = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Record(usize, Vec<UnpoweredFunctionState>);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            controller.push(self.0);
+            self.1.remove(0)
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    fn record(
+        id: usize,
+        results: Vec<UnpoweredFunctionState>,
+    ) -> Box<dyn UnpoweredFunction<Model = (), Controller = Vec<usize>> + Send + Sync> {
+        Box::new(Record(id, results))
+    }
+
+    #[test]
+    fn executor_runs_every_child() {
+        let mut node = Executor::new(vec![
+            record(0, vec![UnpoweredFunctionState::Failed]),
+            record(1, vec![UnpoweredFunctionState::Complete]),
+            record(2, vec![UnpoweredFunctionState::Failed]),
+        ]);
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Complete
+        );
+        assert_eq!(runs, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn executor_fails_when_every_child_fails() {
+        let mut node = Executor::new(vec![
+            record(0, vec![UnpoweredFunctionState::Failed]),
+            record(1, vec![UnpoweredFunctionState::Failed]),
+        ]);
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Failed
+        );
+        assert_eq!(runs, vec![0, 1]);
+    }
+}
@@ -1,14 +1,24 @@
+mod cooldown;
 mod executor;
 mod failer;
 mod inverter;
+mod random_selector;
 mod repeat;
 mod selector;
 mod sequence;
 mod succeeder;
+mod traced;
+mod utility_selector;
+mod wind_up;
+pub use cooldown::*;
 pub use executor::*;
 pub use failer::*;
 pub use inverter::*;
+pub use random_selector::*;
 pub use repeat::*;
 pub use selector::*;
 pub use sequence::*;
 pub use succeeder::*;
+pub use traced::*;
+pub use utility_selector::*;
+pub use wind_up::*;
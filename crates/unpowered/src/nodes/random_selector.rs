@@ -0,0 +1,217 @@
+use crate::*;
+
+pub struct WeightedSelector<M, C> {
+    nodes: Vec<(
+        u32,
+        Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    )>,
+    order: Vec<usize>,
+    position: Option<usize>,
+}
+
+impl<M, C: UnpoweredRandom> WeightedSelector<M, C> {
+    pub fn new(
+        nodes: Vec<(
+            u32,
+            Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+        )>,
+    ) -> Self {
+        WeightedSelector {
+            nodes,
+            order: vec![],
+            position: None,
+        }
+    }
+
+    // Weighted draw without replacement; zero-weight children never run.
+    fn shuffle(&mut self, controller: &mut C) {
+        let mut remaining = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, (weight, _))| *weight > 0)
+            .map(|(index, (weight, _))| (index, *weight))
+            .collect::<Vec<_>>();
+        self.order.clear();
+        while !remaining.is_empty() {
+            let total = remaining.iter().map(|(_, weight)| weight).sum();
+            let mut roll = controller.random_below(total);
+            let mut picked = 0;
+            for (position, (_, weight)) in remaining.iter().enumerate() {
+                if roll < *weight {
+                    picked = position;
+                    break;
+                }
+                roll -= weight;
+            }
+            self.order.push(remaining.remove(picked).0);
+        }
+    }
+}
+
+impl<M: 'static, C: UnpoweredRandom + 'static> UnpoweredFunction for WeightedSelector<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        let mut position = match self.position {
+            Some(position) => position,
+            None => {
+                self.shuffle(controller);
+                0
+            }
+        };
+        loop {
+            if let Some(&index) = self.order.get(position) {
+                let result = self.nodes[index].1.resume_with(model, controller);
+                match result {
+                    UnpoweredFunctionState::Failed => {
+                        // Move on to the next node.
+                        position += 1;
+                    }
+                    UnpoweredFunctionState::Complete => {
+                        self.position = None;
+                        return result;
+                    }
+                    _ => {
+                        // Waiting, NeedsGas
+                        self.position = Some(position);
+                        return result;
+                    }
+                }
+            } else {
+                self.position = None;
+                return UnpoweredFunctionState::Failed;
+            }
+        }
+    }
+
+    fn reset(self: &mut Self, _parameter: &Self::Model) {
+        self.position = None;
+    }
+}
+
+pub struct RandomSelector<M, C>(WeightedSelector<M, C>);
+
+impl<M, C: UnpoweredRandom> RandomSelector<M, C> {
+    pub fn new(
+        nodes: Vec<Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>>,
+    ) -> Self {
+        RandomSelector(WeightedSelector::new(
+            nodes.into_iter().map(|node| (1, node)).collect(),
+        ))
+    }
+}
+
+impl<M: 'static, C: UnpoweredRandom + 'static> UnpoweredFunction for RandomSelector<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        self.0.resume_with(model, controller)
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.0.reset(model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Rolls {
+        rolls: Vec<u32>,
+        ran: Vec<usize>,
+    }
+
+    impl UnpoweredRandom for Rolls {
+        fn random_below(self: &mut Self, range: u32) -> u32 {
+            self.rolls.remove(0) % range
+        }
+    }
+
+    struct Record(usize, UnpoweredFunctionState);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Rolls;
+        fn resume_with(
+            self: &mut Self,
+            _model: &(),
+            controller: &mut Rolls,
+        ) -> UnpoweredFunctionState {
+            controller.ran.push(self.0);
+            self.1
+        }
+
+        fn reset(self: &mut Self, _model: &()) {}
+    }
+
+    fn weighted(children: &[(u32, UnpoweredFunctionState)]) -> WeightedSelector<(), Rolls> {
+        WeightedSelector::new(
+            children
+                .iter()
+                .enumerate()
+                .map(|(index, (weight, result))| {
+                    (*weight, Box::new(Record(index, *result)) as Box<_>)
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn picks_by_weight() {
+        let complete = UnpoweredFunctionState::Complete;
+        let mut controller = Rolls {
+            rolls: vec![69, 0],
+            ..Default::default()
+        };
+        weighted(&[(70, complete), (30, complete)]).resume_with(&(), &mut controller);
+        assert_eq!(controller.ran, vec![0]);
+        let mut controller = Rolls {
+            rolls: vec![70, 0],
+            ..Default::default()
+        };
+        weighted(&[(70, complete), (30, complete)]).resume_with(&(), &mut controller);
+        assert_eq!(controller.ran, vec![1]);
+    }
+
+    #[test]
+    fn falls_back_on_failure() {
+        let mut controller = Rolls {
+            rolls: vec![0, 0, 0],
+            ..Default::default()
+        };
+        let result = weighted(&[
+            (1, UnpoweredFunctionState::Failed),
+            (1, UnpoweredFunctionState::Failed),
+            (1, UnpoweredFunctionState::Complete),
+        ])
+        .resume_with(&(), &mut controller);
+        assert_eq!(result, UnpoweredFunctionState::Complete);
+        assert_eq!(controller.ran, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn skips_zero_weights() {
+        let mut controller = Rolls {
+            rolls: vec![0],
+            ..Default::default()
+        };
+        let result = weighted(&[
+            (0, UnpoweredFunctionState::Complete),
+            (5, UnpoweredFunctionState::Failed),
+        ])
+        .resume_with(&(), &mut controller);
+        assert_eq!(result, UnpoweredFunctionState::Failed);
+        assert_eq!(controller.ran, vec![1]);
+    }
+}
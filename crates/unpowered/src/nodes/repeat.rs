@@ -1,4 +1,4 @@
-use crate::bt::*;
+use crate::*;
 
 pub struct Repeat<M, C> {
     node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
@@ -47,7 +47,7 @@ impl<M: 'static, C: 'static> UnpoweredFunction for Repeat<M, C> {
         return UnpoweredFunctionState::Complete;
     }
 
-    fn reset(self: &mut Self, model: &Self::Model) {
+    fn reset(self: &mut Self, _model: &Self::Model) {
         self.runs_left = self.runs;
     }
 }
@@ -87,7 +87,7 @@ impl<M: 'static, C: 'static> UnpoweredFunction for RepeatUntilFail<M, C> {
         }
     }
 
-    fn reset(self: &mut Self, model: &Self::Model) {
+    fn reset(self: &mut Self, _model: &Self::Model) {
         // Nothing to do.
     }
 }
@@ -128,7 +128,107 @@ impl<M: 'static, C: 'static> UnpoweredFunction for RepeatUntilSuccess<M, C> {
         }
     }
 
-    fn reset(self: &mut Self, model: &Self::Model) {
+    fn reset(self: &mut Self, _model: &Self::Model) {
         // Nothing to do.
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Record(usize, Vec<UnpoweredFunctionState>);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            controller.push(self.0);
+            self.1.remove(0)
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    fn record(
+        id: usize,
+        results: Vec<UnpoweredFunctionState>,
+    ) -> Box<dyn UnpoweredFunction<Model = (), Controller = Vec<usize>> + Send + Sync> {
+        Box::new(Record(id, results))
+    }
+
+    #[test]
+    fn repeat_runs_child_n_times() {
+        let mut node = Repeat::new(record(0, vec![UnpoweredFunctionState::Complete; 3]), 3);
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Complete
+        );
+        assert_eq!(runs.len(), 3);
+    }
+
+    #[test]
+    fn repeat_stops_on_failure() {
+        let mut node = Repeat::new(
+            record(
+                0,
+                vec![
+                    UnpoweredFunctionState::Complete,
+                    UnpoweredFunctionState::Failed,
+                ],
+            ),
+            3,
+        );
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Failed
+        );
+        assert_eq!(runs.len(), 2);
+    }
+
+    #[test]
+    fn repeat_until_fail_completes_on_failure() {
+        let mut node = RepeatUntilFail::new(record(
+            0,
+            vec![
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Failed,
+            ],
+        ));
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Complete
+        );
+        assert_eq!(runs.len(), 3);
+    }
+
+    #[test]
+    fn repeat_until_success_retries_failures() {
+        let mut node = RepeatUntilSuccess::new(record(
+            0,
+            vec![
+                UnpoweredFunctionState::Failed,
+                UnpoweredFunctionState::Waiting,
+                UnpoweredFunctionState::Complete,
+            ],
+        ));
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Waiting
+        );
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Complete
+        );
+        assert_eq!(runs.len(), 3);
+    }
+}
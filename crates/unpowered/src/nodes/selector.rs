@@ -0,0 +1,135 @@
+use crate::*;
+
+pub struct Selector<M, C> {
+    nodes: Vec<Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>>,
+    index: Option<usize>,
+}
+
+impl<M, C> Selector<M, C> {
+    pub fn new(
+        nodes: Vec<Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>>,
+    ) -> Self {
+        Selector { nodes, index: None }
+    }
+}
+
+impl<M: 'static, C: 'static> UnpoweredFunction for Selector<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        let mut running_index = self.index.unwrap_or(0);
+        loop {
+            if let Some(node) = self.nodes.get_mut(running_index) {
+                let result = node.resume_with(model, controller);
+                match result {
+                    UnpoweredFunctionState::Failed => {
+                        // Move on to the next node.
+                        running_index += 1;
+                    }
+                    UnpoweredFunctionState::Complete => {
+                        self.index = None;
+                        return result;
+                    }
+                    _ => {
+                        // Waiting, NeedsGas
+                        self.index = Some(running_index);
+                        return result;
+                    }
+                }
+            } else {
+                self.index = None;
+                return UnpoweredFunctionState::Failed;
+            }
+        }
+    }
+
+    fn reset(self: &mut Self, _parameter: &Self::Model) {
+        self.index = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Record(usize, Vec<UnpoweredFunctionState>);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            controller.push(self.0);
+            self.1.remove(0)
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    fn record(
+        id: usize,
+        results: Vec<UnpoweredFunctionState>,
+    ) -> Box<dyn UnpoweredFunction<Model = (), Controller = Vec<usize>> + Send + Sync> {
+        Box::new(Record(id, results))
+    }
+
+    #[test]
+    fn selector_stops_at_first_success() {
+        let mut node = Selector::new(vec![
+            record(0, vec![UnpoweredFunctionState::Failed]),
+            record(1, vec![UnpoweredFunctionState::Complete]),
+            record(2, vec![UnpoweredFunctionState::Complete]),
+        ]);
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Complete
+        );
+        assert_eq!(runs, vec![0, 1]);
+    }
+
+    #[test]
+    fn selector_fails_when_every_child_fails() {
+        let mut node = Selector::new(vec![
+            record(0, vec![UnpoweredFunctionState::Failed]),
+            record(1, vec![UnpoweredFunctionState::Failed]),
+        ]);
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Failed
+        );
+        assert_eq!(runs, vec![0, 1]);
+    }
+
+    #[test]
+    fn selector_resumes_waiting_child() {
+        let mut node = Selector::new(vec![
+            record(0, vec![UnpoweredFunctionState::Failed]),
+            record(
+                1,
+                vec![
+                    UnpoweredFunctionState::Waiting,
+                    UnpoweredFunctionState::Complete,
+                ],
+            ),
+        ]);
+        let mut runs = vec![];
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Waiting
+        );
+        assert_eq!(
+            node.resume_with(&(), &mut runs),
+            UnpoweredFunctionState::Complete
+        );
+        assert_eq!(runs, vec![0, 1, 1]);
+    }
+}
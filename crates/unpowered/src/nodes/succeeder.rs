@@ -0,0 +1,85 @@
+use crate::*;
+pub struct Succeeder<M, C> {
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+}
+
+impl<M, C> Succeeder<M, C> {
+    pub fn new(node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>) -> Self {
+        Succeeder { node }
+    }
+}
+
+impl<M: 'static, C: 'static> UnpoweredFunction for Succeeder<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        match self.node.resume_with(model, controller) {
+            UnpoweredFunctionState::Failed | UnpoweredFunctionState::Complete => {
+                return UnpoweredFunctionState::Complete;
+            }
+            result => {
+                // Waiting, NeedsGas
+                return result;
+            }
+        }
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.node.reset(model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Record(usize, Vec<UnpoweredFunctionState>);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            controller.push(self.0);
+            self.1.remove(0)
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    fn record(
+        id: usize,
+        results: Vec<UnpoweredFunctionState>,
+    ) -> Box<dyn UnpoweredFunction<Model = (), Controller = Vec<usize>> + Send + Sync> {
+        Box::new(Record(id, results))
+    }
+
+    #[test]
+    fn succeeder_always_completes() {
+        let mut node = Succeeder::new(record(
+            0,
+            vec![
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Failed,
+                UnpoweredFunctionState::Waiting,
+            ],
+        ));
+        let mut runs = vec![];
+        let results: Vec<_> = (0..3).map(|_| node.resume_with(&(), &mut runs)).collect();
+        assert_eq!(
+            results,
+            vec![
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Waiting,
+            ]
+        );
+    }
+}
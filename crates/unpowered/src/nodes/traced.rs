@@ -0,0 +1,110 @@
+use crate::*;
+
+pub struct Traced<M, C> {
+    label: String,
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+}
+
+impl<M, C> Traced<M, C> {
+    pub fn new(
+        label: String,
+        node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    ) -> Self {
+        Traced { label, node }
+    }
+}
+
+impl<M: 'static, C: UnpoweredTracer + 'static> UnpoweredFunction for Traced<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        let index = controller.trace_mut().map(|trace| trace.start(&self.label));
+        let result = self.node.resume_with(model, controller);
+        if let (Some(index), Some(trace)) = (index, controller.trace_mut()) {
+            trace.finish(index, result);
+        }
+        result
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.node.reset(model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Tracer(Option<UnpoweredTrace>);
+
+    impl UnpoweredTracer for Tracer {
+        fn trace_mut(self: &mut Self) -> Option<&mut UnpoweredTrace> {
+            self.0.as_mut()
+        }
+    }
+
+    struct Fixed(UnpoweredFunctionState);
+
+    impl UnpoweredFunction for Fixed {
+        type Model = ();
+        type Controller = Tracer;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            _controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            self.0
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    fn traced(
+        label: &str,
+        node: Box<dyn UnpoweredFunction<Model = (), Controller = Tracer> + Send + Sync>,
+    ) -> Box<dyn UnpoweredFunction<Model = (), Controller = Tracer> + Send + Sync> {
+        Box::new(Traced::new(label.to_string(), node))
+    }
+
+    #[test]
+    fn records_nested_results() {
+        let mut tree = traced(
+            "Selector",
+            Box::new(Selector::new(vec![
+                traced("A", Box::new(Fixed(UnpoweredFunctionState::Failed))),
+                traced("B", Box::new(Fixed(UnpoweredFunctionState::Complete))),
+            ])),
+        );
+        let mut tracer = Tracer(Some(UnpoweredTrace::default()));
+        tree.resume_with(&(), &mut tracer);
+        let entries = tracer.0.unwrap().entries;
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.depth, entry.label.as_str(), entry.state))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, "Selector", Some(UnpoweredFunctionState::Complete)),
+                (1, "A", Some(UnpoweredFunctionState::Failed)),
+                (1, "B", Some(UnpoweredFunctionState::Complete)),
+            ]
+        );
+    }
+
+    #[test]
+    fn records_nothing_when_off() {
+        let mut tree = traced("A", Box::new(Fixed(UnpoweredFunctionState::Complete)));
+        let mut tracer = Tracer(None);
+        assert_eq!(
+            tree.resume_with(&(), &mut tracer),
+            UnpoweredFunctionState::Complete
+        );
+        assert!(tracer.0.is_none());
+    }
+}
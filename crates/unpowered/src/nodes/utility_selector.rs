@@ -0,0 +1,139 @@
+use crate::*;
+
+pub type Scorer<M, C> = Box<dyn UtilityScorer<Model = M, Controller = C> + Send + Sync>;
+pub type Node<M, C> = Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>;
+
+pub struct UtilitySelector<M, C> {
+    nodes: Vec<(Scorer<M, C>, Node<M, C>)>,
+    index: Option<usize>,
+}
+
+impl<M, C> UtilitySelector<M, C> {
+    pub fn new(nodes: Vec<(Scorer<M, C>, Node<M, C>)>) -> Self {
+        UtilitySelector { nodes, index: None }
+    }
+}
+
+impl<M: 'static, C: 'static> UtilitySelector<M, C> {
+    fn best_index(&self, model: &M, controller: &C) -> Option<usize> {
+        let mut best: Option<(usize, f32)> = None;
+        for (index, (scorer, _node)) in self.nodes.iter().enumerate() {
+            let score = scorer.score(model, controller);
+            if score.is_nan() {
+                continue;
+            }
+            // Strictly greater, so ties go to the earliest child.
+            let better = match best {
+                Some((_, best_score)) => score > best_score,
+                None => true,
+            };
+            if better {
+                best = Some((index, score));
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+}
+
+impl<M: 'static, C: 'static> UnpoweredFunction for UtilitySelector<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        let running_index = match self.index {
+            Some(index) => index,
+            None => match self.best_index(model, controller) {
+                Some(index) => index,
+                None => return UnpoweredFunctionState::Failed,
+            },
+        };
+        let (_scorer, node) = &mut self.nodes[running_index];
+        let result = node.resume_with(model, controller);
+        match result {
+            UnpoweredFunctionState::Waiting => {
+                self.index = Some(running_index);
+            }
+            _ => {
+                self.index = None;
+            }
+        }
+        result
+    }
+
+    fn reset(self: &mut Self, _parameter: &Self::Model) {
+        self.index = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Score(f32);
+
+    impl UtilityScorer for Score {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn score(self: &Self, _model: &(), _controller: &Vec<usize>) -> f32 {
+            self.0
+        }
+    }
+
+    struct Record(usize);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn resume_with(
+            self: &mut Self,
+            _model: &(),
+            controller: &mut Vec<usize>,
+        ) -> UnpoweredFunctionState {
+            controller.push(self.0);
+            UnpoweredFunctionState::Complete
+        }
+
+        fn reset(self: &mut Self, _model: &()) {}
+    }
+
+    fn selector(scores: &[f32]) -> UtilitySelector<(), Vec<usize>> {
+        UtilitySelector::new(
+            scores
+                .iter()
+                .enumerate()
+                .map(|(index, score)| {
+                    (
+                        Box::new(Score(*score)) as Box<_>,
+                        Box::new(Record(index)) as Box<_>,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn runs_highest_scoring_child() {
+        let mut ran = vec![];
+        let result = selector(&[1., 3., 2.]).resume_with(&(), &mut ran);
+        assert_eq!(result, UnpoweredFunctionState::Complete);
+        assert_eq!(ran, vec![1]);
+    }
+
+    #[test]
+    fn ties_go_to_first_child() {
+        let mut ran = vec![];
+        selector(&[f32::NAN, 2., 2.]).resume_with(&(), &mut ran);
+        assert_eq!(ran, vec![1]);
+    }
+
+    #[test]
+    fn fails_without_children() {
+        let mut ran = vec![];
+        let result = selector(&[]).resume_with(&(), &mut ran);
+        assert_eq!(result, UnpoweredFunctionState::Failed);
+        assert!(ran.is_empty());
+    }
+}
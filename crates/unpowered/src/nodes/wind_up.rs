@@ -0,0 +1,185 @@
+use crate::*;
+
+// Spends `turns` resumes Waiting before running its child for real.
+pub struct WindUp<M, C> {
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    turns: usize,
+    waited: usize,
+}
+
+impl<M, C> WindUp<M, C> {
+    pub fn new(
+        turns: usize,
+        node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    ) -> Self {
+        WindUp {
+            node,
+            turns,
+            waited: 0,
+        }
+    }
+}
+
+impl<M: 'static, C: 'static> UnpoweredFunction for WindUp<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        if self.waited < self.turns {
+            self.waited += 1;
+            return UnpoweredFunctionState::Waiting;
+        }
+        let result = self.node.resume_with(model, controller);
+        if result != UnpoweredFunctionState::Waiting {
+            self.waited = 0;
+        }
+        result
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.waited = 0;
+        self.node.reset(model);
+    }
+}
+
+// Runs its child once per resume, Waiting in between, until it has completed `turns` times.
+pub struct Channel<M, C> {
+    node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    turns: usize,
+    channelled: usize,
+}
+
+impl<M, C> Channel<M, C> {
+    pub fn new(
+        turns: usize,
+        node: Box<dyn UnpoweredFunction<Model = M, Controller = C> + Send + Sync>,
+    ) -> Self {
+        Channel {
+            node,
+            turns: turns.max(1),
+            channelled: 0,
+        }
+    }
+}
+
+impl<M: 'static, C: 'static> UnpoweredFunction for Channel<M, C> {
+    type Model = M;
+    type Controller = C;
+    fn resume_with(
+        self: &mut Self,
+        model: &Self::Model,
+        controller: &mut Self::Controller,
+    ) -> UnpoweredFunctionState {
+        match self.node.resume_with(model, controller) {
+            UnpoweredFunctionState::Complete => {
+                self.channelled += 1;
+                if self.channelled < self.turns {
+                    UnpoweredFunctionState::Waiting
+                } else {
+                    self.channelled = 0;
+                    UnpoweredFunctionState::Complete
+                }
+            }
+            UnpoweredFunctionState::Failed => {
+                // Interrupted, so the next channel starts over.
+                self.channelled = 0;
+                UnpoweredFunctionState::Failed
+            }
+            UnpoweredFunctionState::Waiting => UnpoweredFunctionState::Waiting,
+        }
+    }
+
+    fn reset(self: &mut Self, model: &Self::Model) {
+        self.channelled = 0;
+        self.node.reset(model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Record(Vec<UnpoweredFunctionState>);
+
+    impl UnpoweredFunction for Record {
+        type Model = ();
+        type Controller = Vec<usize>;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            controller.push(controller.len());
+            self.0.remove(0)
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    #[test]
+    fn wind_up_waits_before_running() {
+        let mut node = WindUp::new(
+            2,
+            Box::new(Record(vec![
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Complete,
+            ])),
+        );
+        let mut runs = vec![];
+        let results: Vec<_> = (0..4).map(|_| node.resume_with(&(), &mut runs)).collect();
+        assert_eq!(
+            results,
+            vec![
+                UnpoweredFunctionState::Waiting,
+                UnpoweredFunctionState::Waiting,
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Waiting,
+            ]
+        );
+        assert_eq!(runs.len(), 1);
+    }
+
+    #[test]
+    fn channel_runs_every_turn() {
+        let mut node = Channel::new(
+            3,
+            Box::new(Record(vec![UnpoweredFunctionState::Complete; 3])),
+        );
+        let mut runs = vec![];
+        let results: Vec<_> = (0..3).map(|_| node.resume_with(&(), &mut runs)).collect();
+        assert_eq!(
+            results,
+            vec![
+                UnpoweredFunctionState::Waiting,
+                UnpoweredFunctionState::Waiting,
+                UnpoweredFunctionState::Complete,
+            ]
+        );
+        assert_eq!(runs.len(), 3);
+    }
+
+    #[test]
+    fn channel_restarts_after_failure() {
+        let mut node = Channel::new(
+            2,
+            Box::new(Record(vec![
+                UnpoweredFunctionState::Complete,
+                UnpoweredFunctionState::Failed,
+                UnpoweredFunctionState::Complete,
+            ])),
+        );
+        let mut runs = vec![];
+        let results: Vec<_> = (0..3).map(|_| node.resume_with(&(), &mut runs)).collect();
+        assert_eq!(
+            results,
+            vec![
+                UnpoweredFunctionState::Waiting,
+                UnpoweredFunctionState::Failed,
+                UnpoweredFunctionState::Waiting,
+            ]
+        );
+    }
+}
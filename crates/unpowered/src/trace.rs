@@ -0,0 +1,36 @@
+use super::UnpoweredFunctionState;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    // How many traced nodes were still running when this one started.
+    pub depth: usize,
+    pub label: String,
+    // None until the node returns.
+    pub state: Option<UnpoweredFunctionState>,
+}
+
+// Every node resumed during one run of a tree, in the order they started.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnpoweredTrace {
+    pub entries: Vec<TraceEntry>,
+    depth: usize,
+}
+
+impl UnpoweredTrace {
+    pub fn start(&mut self, label: &str) -> usize {
+        self.entries.push(TraceEntry {
+            depth: self.depth,
+            label: label.to_string(),
+            state: None,
+        });
+        self.depth += 1;
+        self.entries.len() - 1
+    }
+
+    pub fn finish(&mut self, index: usize, state: UnpoweredFunctionState) {
+        self.depth = self.depth.saturating_sub(1);
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.state = Some(state);
+        }
+    }
+}
@@ -0,0 +1,597 @@
+use std::fmt::{self, Debug};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    nodes::*, UnpoweredClock, UnpoweredFunction, UnpoweredRandom, UnpoweredTracer, UtilityScorer,
+};
+
+// With the bevy feature, tree definitions load as assets, which needs a TypePath for every part.
+#[cfg(feature = "bevy")]
+pub trait DefinitionPath: bevy::reflect::TypePath {}
+#[cfg(feature = "bevy")]
+impl<T: bevy::reflect::TypePath> DefinitionPath for T {}
+#[cfg(not(feature = "bevy"))]
+pub trait DefinitionPath {}
+#[cfg(not(feature = "bevy"))]
+impl<T> DefinitionPath for T {}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "bevy", derive(bevy::asset::Asset, bevy::reflect::TypePath))]
+pub enum UnpoweredTreeDef<
+    U: UserNodeDefinition + Sync + Send + DefinitionPath,
+    W: UserWrapperDefinition<U> + Sync + Send + DefinitionPath,
+    S: UserScorerDefinition<U> + Sync + Send + DefinitionPath = NoScorer,
+> {
+    Sequence(Vec<UnpoweredTreeDef<U, W, S>>),
+    Selector(Vec<UnpoweredTreeDef<U, W, S>>),
+    Executor(Vec<UnpoweredTreeDef<U, W, S>>),
+    UtilitySelector(Vec<(S, UnpoweredTreeDef<U, W, S>)>),
+    RandomSelector(Vec<UnpoweredTreeDef<U, W, S>>),
+    WeightedSelector(Vec<(u32, UnpoweredTreeDef<U, W, S>)>),
+    Repeat(Box<UnpoweredTreeDef<U, W, S>>, usize),
+    RepeatUntilSuccess(Box<UnpoweredTreeDef<U, W, S>>),
+    RepeatUntilFail(Box<UnpoweredTreeDef<U, W, S>>),
+    Succeeder(Box<UnpoweredTreeDef<U, W, S>>),
+    Failer(Box<UnpoweredTreeDef<U, W, S>>),
+    Inverter(Box<UnpoweredTreeDef<U, W, S>>),
+    Cooldown(usize, Box<UnpoweredTreeDef<U, W, S>>),
+    Once(Box<UnpoweredTreeDef<U, W, S>>),
+    EveryNTurns(usize, Box<UnpoweredTreeDef<U, W, S>>),
+    WindUp(usize, Box<UnpoweredTreeDef<U, W, S>>),
+    Channel(usize, Box<UnpoweredTreeDef<U, W, S>>),
+    User(U),
+    Wrapper(W, Vec<UnpoweredTreeDef<U, W, S>>),
+    // Another tree by name, inlined by resolve_includes before create_tree.
+    Include(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncludeError {
+    Missing(String),
+    // Every name from the first repeat back around to itself.
+    Cycle(Vec<String>),
+    // An Include left in a tree passed to create_tree.
+    Unresolved(String),
+}
+
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncludeError::Missing(name) => write!(f, "included tree {} does not exist", name),
+            IncludeError::Cycle(names) => write!(f, "include cycle: {}", names.join(" > ")),
+            IncludeError::Unresolved(name) => {
+                write!(
+                    f,
+                    "Include({}) was not resolved before creating the tree",
+                    name
+                )
+            }
+        }
+    }
+}
+
+pub trait UserNodeDefinition {
+    type Model: 'static;
+    type Controller: 'static;
+
+    fn create_node(
+        &self,
+    ) -> Box<dyn UnpoweredFunction<Model = Self::Model, Controller = Self::Controller> + Send + Sync>;
+}
+
+impl<M: 'static, C: 'static, D: 'static> UserNodeDefinition for D
+where
+    D: UnpoweredFunction<Model = M, Controller = C> + Clone + Send + Sync,
+{
+    type Model = M;
+    type Controller = C;
+
+    fn create_node(
+        &self,
+    ) -> Box<dyn UnpoweredFunction<Model = Self::Model, Controller = Self::Controller> + Send + Sync>
+    {
+        Box::new(self.clone())
+    }
+}
+
+pub trait UserWrapperDefinition<U: UserNodeDefinition> {
+    fn create_node_and_wrap(
+        &self,
+        nodes: Vec<
+            Box<dyn UnpoweredFunction<Model = U::Model, Controller = U::Controller> + Send + Sync>,
+        >,
+    ) -> Box<dyn UnpoweredFunction<Model = U::Model, Controller = U::Controller> + Send + Sync>;
+}
+
+// For trees without custom wrappers. It has no values, so no tree can name one.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(bevy::reflect::TypePath))]
+pub enum NoWrapper {}
+
+impl<U: UserNodeDefinition> UserWrapperDefinition<U> for NoWrapper {
+    fn create_node_and_wrap(
+        &self,
+        _nodes: Vec<
+            Box<dyn UnpoweredFunction<Model = U::Model, Controller = U::Controller> + Send + Sync>,
+        >,
+    ) -> Box<dyn UnpoweredFunction<Model = U::Model, Controller = U::Controller> + Send + Sync>
+    {
+        match *self {}
+    }
+}
+
+pub trait UserScorerDefinition<U: UserNodeDefinition> {
+    fn create_scorer(
+        &self,
+    ) -> Box<dyn UtilityScorer<Model = U::Model, Controller = U::Controller> + Send + Sync>;
+}
+
+impl<U: UserNodeDefinition, D: 'static> UserScorerDefinition<U> for D
+where
+    D: UtilityScorer<Model = U::Model, Controller = U::Controller> + Clone + Send + Sync,
+{
+    fn create_scorer(
+        &self,
+    ) -> Box<dyn UtilityScorer<Model = U::Model, Controller = U::Controller> + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+// For trees without a UtilitySelector, like NoWrapper.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(bevy::reflect::TypePath))]
+pub enum NoScorer {}
+
+impl<U: UserNodeDefinition> UserScorerDefinition<U> for NoScorer {
+    fn create_scorer(
+        &self,
+    ) -> Box<dyn UtilityScorer<Model = U::Model, Controller = U::Controller> + Send + Sync> {
+        match *self {}
+    }
+}
+
+impl<
+        U: UserNodeDefinition + Send + Sync + DefinitionPath + Debug,
+        W: UserWrapperDefinition<U> + Send + Sync + DefinitionPath + Debug,
+        S: UserScorerDefinition<U> + Send + Sync + DefinitionPath,
+    > UnpoweredTreeDef<U, W, S>
+{
+    // Names the node itself, without its children, for traces and warnings.
+    pub fn label(&self) -> String {
+        match self {
+            UnpoweredTreeDef::Sequence(_) => "Sequence".to_string(),
+            UnpoweredTreeDef::Selector(_) => "Selector".to_string(),
+            UnpoweredTreeDef::Executor(_) => "Executor".to_string(),
+            UnpoweredTreeDef::UtilitySelector(_) => "UtilitySelector".to_string(),
+            UnpoweredTreeDef::RandomSelector(_) => "RandomSelector".to_string(),
+            UnpoweredTreeDef::WeightedSelector(_) => "WeightedSelector".to_string(),
+            UnpoweredTreeDef::Repeat(_, repeats) => format!("Repeat({})", repeats),
+            UnpoweredTreeDef::RepeatUntilSuccess(_) => "RepeatUntilSuccess".to_string(),
+            UnpoweredTreeDef::RepeatUntilFail(_) => "RepeatUntilFail".to_string(),
+            UnpoweredTreeDef::Succeeder(_) => "Succeeder".to_string(),
+            UnpoweredTreeDef::Failer(_) => "Failer".to_string(),
+            UnpoweredTreeDef::Inverter(_) => "Inverter".to_string(),
+            UnpoweredTreeDef::Cooldown(turns, _) => format!("Cooldown({})", turns),
+            UnpoweredTreeDef::Once(_) => "Once".to_string(),
+            UnpoweredTreeDef::EveryNTurns(turns, _) => format!("EveryNTurns({})", turns),
+            UnpoweredTreeDef::WindUp(turns, _) => format!("WindUp({})", turns),
+            UnpoweredTreeDef::Channel(turns, _) => format!("Channel({})", turns),
+            UnpoweredTreeDef::User(node_def) => format!("{:?}", node_def),
+            UnpoweredTreeDef::Wrapper(wrapper_def, _) => format!("{:?}", wrapper_def),
+            UnpoweredTreeDef::Include(name) => format!("Include({})", name),
+        }
+    }
+}
+
+impl<
+        U: UserNodeDefinition + Send + Sync + DefinitionPath + Clone,
+        W: UserWrapperDefinition<U> + Send + Sync + DefinitionPath + Clone,
+        S: UserScorerDefinition<U> + Send + Sync + DefinitionPath + Clone,
+    > UnpoweredTreeDef<U, W, S>
+{
+    // Copies this tree named `name`, with every Include replaced by the tree `find` returns.
+    pub fn resolve_includes<'a>(
+        &self,
+        name: &str,
+        find: &impl Fn(&str) -> Option<&'a Self>,
+    ) -> Result<Self, IncludeError>
+    where
+        Self: 'a,
+    {
+        self.resolve_with(find, &mut vec![name.to_string()])
+    }
+
+    fn resolve_with<'a>(
+        &self,
+        find: &impl Fn(&str) -> Option<&'a Self>,
+        names: &mut Vec<String>,
+    ) -> Result<Self, IncludeError>
+    where
+        Self: 'a,
+    {
+        let resolve_all = |node_defs: &Vec<Self>, names: &mut Vec<String>| {
+            node_defs
+                .iter()
+                .map(|node_def| node_def.resolve_with(find, names))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match self {
+            UnpoweredTreeDef::Sequence(node_defs) => {
+                UnpoweredTreeDef::Sequence(resolve_all(node_defs, names)?)
+            }
+            UnpoweredTreeDef::Selector(node_defs) => {
+                UnpoweredTreeDef::Selector(resolve_all(node_defs, names)?)
+            }
+            UnpoweredTreeDef::Executor(node_defs) => {
+                UnpoweredTreeDef::Executor(resolve_all(node_defs, names)?)
+            }
+            UnpoweredTreeDef::RandomSelector(node_defs) => {
+                UnpoweredTreeDef::RandomSelector(resolve_all(node_defs, names)?)
+            }
+            UnpoweredTreeDef::UtilitySelector(scored_defs) => UnpoweredTreeDef::UtilitySelector(
+                scored_defs
+                    .iter()
+                    .map(|(scorer_def, node_def)| {
+                        Ok((scorer_def.clone(), node_def.resolve_with(find, names)?))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            UnpoweredTreeDef::WeightedSelector(weighted_defs) => {
+                UnpoweredTreeDef::WeightedSelector(
+                    weighted_defs
+                        .iter()
+                        .map(|(weight, node_def)| {
+                            Ok((*weight, node_def.resolve_with(find, names)?))
+                        })
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            UnpoweredTreeDef::Repeat(node_def, repeats) => {
+                UnpoweredTreeDef::Repeat(Box::new(node_def.resolve_with(find, names)?), *repeats)
+            }
+            UnpoweredTreeDef::RepeatUntilSuccess(node_def) => {
+                UnpoweredTreeDef::RepeatUntilSuccess(Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::RepeatUntilFail(node_def) => {
+                UnpoweredTreeDef::RepeatUntilFail(Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::Succeeder(node_def) => {
+                UnpoweredTreeDef::Succeeder(Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::Failer(node_def) => {
+                UnpoweredTreeDef::Failer(Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::Inverter(node_def) => {
+                UnpoweredTreeDef::Inverter(Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::Cooldown(turns, node_def) => {
+                UnpoweredTreeDef::Cooldown(*turns, Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::Once(node_def) => {
+                UnpoweredTreeDef::Once(Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::EveryNTurns(turns, node_def) => {
+                UnpoweredTreeDef::EveryNTurns(*turns, Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::WindUp(turns, node_def) => {
+                UnpoweredTreeDef::WindUp(*turns, Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::Channel(turns, node_def) => {
+                UnpoweredTreeDef::Channel(*turns, Box::new(node_def.resolve_with(find, names)?))
+            }
+            UnpoweredTreeDef::User(node_def) => UnpoweredTreeDef::User(node_def.clone()),
+            UnpoweredTreeDef::Wrapper(wrapper_def, node_defs) => {
+                UnpoweredTreeDef::Wrapper(wrapper_def.clone(), resolve_all(node_defs, names)?)
+            }
+            UnpoweredTreeDef::Include(name) => {
+                if let Some(start) = names.iter().position(|included| included == name) {
+                    let mut cycle = names[start..].to_vec();
+                    cycle.push(name.clone());
+                    return Err(IncludeError::Cycle(cycle));
+                }
+                let included = find(name).ok_or_else(|| IncludeError::Missing(name.clone()))?;
+                names.push(name.clone());
+                let resolved = included.resolve_with(find, names)?;
+                names.pop();
+                resolved
+            }
+        })
+    }
+}
+
+impl<
+        U: UserNodeDefinition + Send + Sync + DefinitionPath + Debug,
+        W: UserWrapperDefinition<U> + Send + Sync + DefinitionPath + Debug,
+        S: UserScorerDefinition<U> + Send + Sync + DefinitionPath,
+    > UnpoweredTreeDef<U, W, S>
+where
+    U::Controller: UnpoweredRandom + UnpoweredClock + UnpoweredTracer,
+{
+    // Fails on an Include, which resolve_includes has to replace first.
+    pub fn create_tree(
+        &self,
+    ) -> Result<
+        Box<dyn UnpoweredFunction<Model = U::Model, Controller = U::Controller> + Send + Sync>,
+        IncludeError,
+    > {
+        let node: Box<
+            dyn UnpoweredFunction<Model = U::Model, Controller = U::Controller> + Send + Sync,
+        > = match self {
+            UnpoweredTreeDef::Sequence(node_defs) => {
+                let nodes = node_defs
+                    .iter()
+                    .map(|node_def| node_def.create_tree())
+                    .collect::<Result<_, _>>()?;
+                Box::new(Sequence::new(nodes))
+            }
+            UnpoweredTreeDef::Selector(node_defs) => {
+                let nodes = node_defs
+                    .iter()
+                    .map(|node_def| node_def.create_tree())
+                    .collect::<Result<_, _>>()?;
+                Box::new(Selector::new(nodes))
+            }
+            UnpoweredTreeDef::Executor(node_defs) => {
+                let nodes = node_defs
+                    .iter()
+                    .map(|node_def| node_def.create_tree())
+                    .collect::<Result<_, _>>()?;
+                Box::new(Executor::new(nodes))
+            }
+            UnpoweredTreeDef::UtilitySelector(scored_defs) => {
+                let nodes = scored_defs
+                    .iter()
+                    .map(|(scorer_def, node_def)| {
+                        Ok((scorer_def.create_scorer(), node_def.create_tree()?))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Box::new(UtilitySelector::new(nodes))
+            }
+            UnpoweredTreeDef::RandomSelector(node_defs) => {
+                let nodes = node_defs
+                    .iter()
+                    .map(|node_def| node_def.create_tree())
+                    .collect::<Result<_, _>>()?;
+                Box::new(RandomSelector::new(nodes))
+            }
+            UnpoweredTreeDef::WeightedSelector(weighted_defs) => {
+                let nodes = weighted_defs
+                    .iter()
+                    .map(|(weight, node_def)| Ok((*weight, node_def.create_tree()?)))
+                    .collect::<Result<_, _>>()?;
+                Box::new(WeightedSelector::new(nodes))
+            }
+            UnpoweredTreeDef::Repeat(node_def, repeats) => {
+                let node = node_def.create_tree()?;
+                Box::new(Repeat::new(node, *repeats))
+            }
+            UnpoweredTreeDef::RepeatUntilFail(node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(RepeatUntilFail::new(node))
+            }
+            UnpoweredTreeDef::RepeatUntilSuccess(node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(RepeatUntilSuccess::new(node))
+            }
+            UnpoweredTreeDef::Succeeder(node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(Succeeder::new(node))
+            }
+            UnpoweredTreeDef::Inverter(node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(Inverter::new(node))
+            }
+            UnpoweredTreeDef::Failer(node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(Failer::new(node))
+            }
+            UnpoweredTreeDef::Cooldown(turns, node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(Cooldown::new(*turns, node))
+            }
+            UnpoweredTreeDef::Once(node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(Once::new(node))
+            }
+            UnpoweredTreeDef::EveryNTurns(turns, node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(EveryNTurns::new(*turns, node))
+            }
+            UnpoweredTreeDef::WindUp(turns, node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(WindUp::new(*turns, node))
+            }
+            UnpoweredTreeDef::Channel(turns, node_def) => {
+                let node = node_def.create_tree()?;
+                Box::new(Channel::new(*turns, node))
+            }
+            UnpoweredTreeDef::User(node_def) => node_def.create_node(),
+            UnpoweredTreeDef::Wrapper(wrapper_def, node_defs) => {
+                let nodes = node_defs
+                    .iter()
+                    .map(|node_def| node_def.create_tree())
+                    .collect::<Result<_, _>>()?;
+                wrapper_def.create_node_and_wrap(nodes)
+            }
+            UnpoweredTreeDef::Include(name) => {
+                return Err(IncludeError::Unresolved(name.clone()));
+            }
+        };
+        Ok(Box::new(Traced::new(self.label(), node)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{UnpoweredFunctionState, UnpoweredTrace};
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[cfg_attr(feature = "bevy", derive(bevy::reflect::TypePath))]
+    enum Leaf {
+        Pass,
+        Fail,
+    }
+
+    #[derive(Default)]
+    struct Runner(Option<UnpoweredTrace>);
+
+    impl UnpoweredRandom for Runner {}
+    impl UnpoweredClock for Runner {}
+
+    impl UnpoweredTracer for Runner {
+        fn trace_mut(self: &mut Self) -> Option<&mut UnpoweredTrace> {
+            self.0.as_mut()
+        }
+    }
+
+    impl UnpoweredFunction for Leaf {
+        type Model = ();
+        type Controller = Runner;
+        fn resume_with(
+            self: &mut Self,
+            _model: &Self::Model,
+            _controller: &mut Self::Controller,
+        ) -> UnpoweredFunctionState {
+            match self {
+                Leaf::Pass => UnpoweredFunctionState::Complete,
+                Leaf::Fail => UnpoweredFunctionState::Failed,
+            }
+        }
+
+        fn reset(self: &mut Self, _model: &Self::Model) {}
+    }
+
+    type Def = UnpoweredTreeDef<Leaf, NoWrapper>;
+
+    fn parse(source: &str) -> Def {
+        ron::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn built_trees_trace_every_node() {
+        let mut tree = parse(
+            "Selector([
+                Sequence([User(Pass), Inverter(User(Pass))]),
+                Executor([Succeeder(User(Fail)), Repeat(User(Pass), 2)]),
+            ])",
+        )
+        .create_tree()
+        .unwrap();
+        let mut runner = Runner(Some(UnpoweredTrace::default()));
+        assert_eq!(
+            tree.resume_with(&(), &mut runner),
+            UnpoweredFunctionState::Complete
+        );
+        let summary: Vec<_> = runner
+            .0
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| (entry.depth, entry.label, entry.state.unwrap()))
+            .collect();
+        let expected = [
+            (0, "Selector", UnpoweredFunctionState::Complete),
+            (1, "Sequence", UnpoweredFunctionState::Failed),
+            (2, "Pass", UnpoweredFunctionState::Complete),
+            (2, "Inverter", UnpoweredFunctionState::Failed),
+            (3, "Pass", UnpoweredFunctionState::Complete),
+            (1, "Executor", UnpoweredFunctionState::Complete),
+            (2, "Succeeder", UnpoweredFunctionState::Complete),
+            (3, "Fail", UnpoweredFunctionState::Failed),
+            (2, "Repeat(2)", UnpoweredFunctionState::Complete),
+            (3, "Pass", UnpoweredFunctionState::Complete),
+            (3, "Pass", UnpoweredFunctionState::Complete),
+        ];
+        assert_eq!(
+            summary,
+            expected
+                .map(|(depth, label, state)| (depth, label.to_string(), state))
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn includes_are_inlined() {
+        let trees = HashMap::from([
+            (
+                "root".to_string(),
+                parse("Selector([Include(\"fail\"), Include(\"pass\")])"),
+            ),
+            ("pass".to_string(), parse("Succeeder(User(Fail))")),
+            ("fail".to_string(), parse("Inverter(Include(\"pass\"))")),
+        ]);
+        let find = |name: &str| trees.get(name);
+        let mut tree = trees["root"]
+            .resolve_includes("root", &find)
+            .unwrap()
+            .create_tree()
+            .unwrap();
+        let mut runner = Runner(Some(UnpoweredTrace::default()));
+        assert_eq!(
+            tree.resume_with(&(), &mut runner),
+            UnpoweredFunctionState::Complete
+        );
+        let labels: Vec<_> = runner
+            .0
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| entry.label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Selector",
+                "Inverter",
+                "Succeeder",
+                "Fail",
+                "Succeeder",
+                "Fail"
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_includes_are_reported() {
+        let def = parse("Sequence([User(Pass), Include(\"patrol\")])");
+        assert_eq!(
+            def.resolve_includes("guard", &|_| None).err(),
+            Some(IncludeError::Missing("patrol".to_string()))
+        );
+        // Building without resolving reports the Include instead of panicking.
+        assert_eq!(
+            def.create_tree().err(),
+            Some(IncludeError::Unresolved("patrol".to_string()))
+        );
+    }
+
+    #[test]
+    fn include_cycles_are_reported() {
+        let trees = HashMap::from([
+            (
+                "a".to_string(),
+                parse("Selector([User(Fail), Include(\"b\")])"),
+            ),
+            ("b".to_string(), parse("Once(Include(\"c\"))")),
+            ("c".to_string(), parse("Include(\"b\")")),
+        ]);
+        let find = |name: &str| trees.get(name);
+        assert_eq!(
+            trees["a"].resolve_includes("a", &find).err(),
+            Some(IncludeError::Cycle(vec![
+                "b".to_string(),
+                "c".to_string(),
+                "b".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn trees_without_wrappers_reject_them_when_parsed() {
+        assert!(ron::from_str::<Def>("Wrapper(Anything, [User(Pass)])").is_err());
+        assert!(ron::from_str::<Def>("UtilitySelector([(Anything, User(Pass))])").is_err());
+    }
+}
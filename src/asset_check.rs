@@ -0,0 +1,360 @@
+use std::{fmt, path::Path};
+
+use crate::{
+    battle::validate_brain,
+    headless::HeadlessAssets,
+    loading::CONTENT_MANIFEST,
+    locale::{ENGLISH, LOCALES_FOLDER},
+    mods::MODS_FOLDER,
+    prelude::*,
+    summoner::SYNERGIES_FILE,
+    summons::Tribe,
+};
+
+const TRIBES: [Tribe; 7] = [
+    Tribe::Angel,
+    Tribe::Undead,
+    Tribe::Fairy,
+    Tribe::Construct,
+    Tribe::Elemental,
+    Tribe::Demon,
+    Tribe::Enemy,
+];
+
+// Tiles.png is cut into an 8x8 atlas.
+const ATLAS_TILES: usize = 8 * 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetProblem {
+    // The asset's path under assets/.
+    pub asset: String,
+    pub message: String,
+}
+
+impl fmt::Display for AssetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.asset, self.message)
+    }
+}
+
+// Finds the content mistakes that would otherwise only show up mid-game, as a panic or a
+// brain that never does what it says.
+pub fn check_assets(assets: &HeadlessAssets) -> Vec<AssetProblem> {
+    let mut problems = vec![];
+    let mut problem = |asset: &str, message: String| {
+        problems.push(AssetProblem {
+            asset: asset.to_string(),
+            message,
+        })
+    };
+    for file in &assets.unlisted {
+        problem(
+            file,
+            format!(
+                "is not listed in {}, so the game never loads it",
+                CONTENT_MANIFEST
+            ),
+        );
+    }
+    for skipped in &assets.packs.skipped {
+        problem(MODS_FOLDER, format!("skipped: {}", skipped));
+    }
+    for conflict in &assets.packs.conflicts {
+        problem(MODS_FOLDER, format!("conflict: {}", conflict));
+    }
+    let mut summons: Vec<_> = assets
+        .player_summons
+        .iter()
+        .map(|(stem, summon)| (format!("summons/{}.summon", stem), summon))
+        .chain(
+            assets
+                .npc_summons
+                .iter()
+                .map(|(stem, summon)| (format!("npc/{}.summon", stem), summon)),
+        )
+        .chain(
+            assets
+                .fused_summons
+                .iter()
+                .map(|(stem, summon)| (format!("fused/{}.summon", stem), summon)),
+        )
+        .collect();
+    summons.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut short_codes: HashMap<&str, &str> = HashMap::new();
+    for (path, summon) in &summons {
+        if !assets.brains.contains_key(summon.brain()) {
+            problem(path, format!("brain \"{}\" does not exist", summon.brain()));
+        }
+        let death_brain = summon.death_brain();
+        if !death_brain.is_empty() && !assets.brains.contains_key(death_brain) {
+            problem(
+                path,
+                format!("death_brain \"{}\" does not exist", death_brain),
+            );
+        }
+        if let Some(other) = short_codes.insert(summon.short_code(), path) {
+            problem(
+                path,
+                format!(
+                    "short_code \"{}\" is also used by {}",
+                    summon.short_code(),
+                    other
+                ),
+            );
+        }
+        if let (_, Some(prerequisite)) = summon.prerequisites() {
+            if !summons
+                .iter()
+                .any(|(_, other)| other.name() == prerequisite)
+            {
+                problem(
+                    path,
+                    format!("prerequisite \"{}\" is not a summon's name", prerequisite),
+                );
+            }
+        }
+        if summon
+            .levels()
+            .windows(2)
+            .any(|pair| pair[0].experience >= pair[1].experience)
+        {
+            problem(path, "levels need increasing experience".to_string());
+        }
+        for sound in [
+            &summon.sounds().summon,
+            &summon.sounds().attack,
+            &summon.sounds().hurt,
+            &summon.sounds().death,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !assets.sounds.contains(sound) {
+                problem(path, format!("sound \"{}\" is not in sounds/", sound));
+            }
+        }
+        if summon.sprite_idx() >= ATLAS_TILES {
+            problem(
+                path,
+                format!(
+                    "sprite_idx {} is outside the {} tile atlas",
+                    summon.sprite_idx(),
+                    ATLAS_TILES
+                ),
+            );
+        }
+    }
+    // Summons without their own sounds fall back to these.
+    for tribe in TRIBES {
+        for sting in [tribe.sting(), tribe.death_sting()] {
+            if !assets.sounds.iter().any(|sound| sound == sting) {
+                problem(
+                    CONTENT_MANIFEST,
+                    format!("{:?} needs \"sounds/{}.wav\"", tribe, sting),
+                );
+            }
+        }
+    }
+    let mut brains: Vec<_> = assets.brains.keys().collect();
+    brains.sort();
+    for name in brains {
+        let path = format!("brains/{}.brain", name);
+        match assets.brain(name) {
+            Ok(brain) => {
+                for warning in validate_brain(&brain) {
+                    problem(&path, warning.to_string());
+                }
+            }
+            Err(err) => problem(&path, err.to_string()),
+        }
+    }
+    for (name, wave) in &assets.waves {
+        let path = format!("waves/{}.wave", name);
+        let mut units: Vec<_> = wave.iter().map(|(_, summon)| summon).collect();
+        units.sort();
+        units.dedup();
+        for summon in units {
+            if assets.summon(summon).is_none() {
+                problem(&path, format!("unit \"{}\" does not exist", summon));
+            }
+        }
+    }
+    for (name, fusion) in &assets.fusions {
+        let path = format!("fusions/{}.fusion", name);
+        for input in [&fusion.inputs.0, &fusion.inputs.1] {
+            if !assets.player_summons.contains_key(input)
+                && !assets.fused_summons.contains_key(input)
+            {
+                problem(
+                    &path,
+                    format!("input \"{}\" is not a summon the player can own", input),
+                );
+            }
+        }
+        if fusion.inputs.0 == fusion.inputs.1 {
+            problem(&path, "inputs need to be two different summons".to_string());
+        }
+        if !assets.fused_summons.contains_key(&fusion.output) {
+            problem(
+                &path,
+                format!("output \"{}\" is not in fused/", fusion.output),
+            );
+        }
+    }
+    for (index, synergy) in assets.synergies.0.iter().enumerate() {
+        if assets.synergies.0[..index]
+            .iter()
+            .any(|other| other.tribe == synergy.tribe)
+        {
+            problem(
+                SYNERGIES_FILE,
+                format!("{:?} has more than one synergy", synergy.tribe),
+            );
+        }
+        if synergy
+            .tiers
+            .windows(2)
+            .any(|pair| pair[0].units >= pair[1].units)
+        {
+            problem(
+                SYNERGIES_FILE,
+                format!("{:?} tiers need increasing units", synergy.tribe),
+            );
+        }
+    }
+    for (language, key) in assets.localization.unknown_keys() {
+        problem(
+            &format!("{}/{}.locale", LOCALES_FOLDER, language),
+            format!(
+                "\"{}\" is not in {}.locale, so it is never shown",
+                key, ENGLISH
+            ),
+        );
+    }
+    let story = &assets.story;
+    for wave in &story.waves {
+        if !assets.waves.iter().any(|(name, _)| name == wave) {
+            problem("story.teller", format!("wave \"{}\" does not exist", wave));
+        }
+    }
+    // One agnostic beat plays between each pair of waves.
+    for (beats, expected, length) in [
+        (
+            "winning_beats",
+            story.waves.len(),
+            story.winning_beats.len(),
+        ),
+        ("losing_beats", story.waves.len(), story.losing_beats.len()),
+        (
+            "agnostic_beats",
+            story.waves.len().saturating_sub(1),
+            story.agnostic_beats.len(),
+        ),
+    ] {
+        if length != expected {
+            problem(
+                "story.teller",
+                format!(
+                    "{} has {} entries for {} waves, expected {}",
+                    beats,
+                    length,
+                    story.waves.len(),
+                    expected
+                ),
+            );
+        }
+    }
+    problems
+}
+
+// Entry point for `cargo run -- check-assets [DIR]`, which exits non-zero if anything is wrong.
+pub fn run_check_assets(mut args: impl Iterator<Item = String>) {
+    let root = args.next().unwrap_or_else(|| "assets".to_string());
+    let assets = match HeadlessAssets::load(Path::new(&root)) {
+        Ok(assets) => assets,
+        Err(err) => {
+            eprintln!("Could not load assets: {}", err);
+            std::process::exit(1);
+        }
+    };
+    let problems = check_assets(&assets);
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!("No problems found in {}", root);
+    } else {
+        println!("{} problems found in {}", problems.len(), root);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shipped() -> HeadlessAssets {
+        HeadlessAssets::load(Path::new("assets")).unwrap()
+    }
+
+    #[test]
+    fn shipped_assets_are_consistent() {
+        let problems = check_assets(&shipped());
+        assert!(
+            problems.is_empty(),
+            "{}",
+            problems
+                .iter()
+                .map(|problem| problem.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn broken_references_are_reported() {
+        let mut assets = shipped();
+        assets.brains.remove("demon");
+        assets.brains.insert(
+            "reckless".to_string(),
+            ron::from_str("Sequence([User(FindNearestEnemy), User(AttackTarget)])").unwrap(),
+        );
+        assets.brains.insert(
+            "lost".to_string(),
+            ron::from_str("Selector([Include(\"demon\")])").unwrap(),
+        );
+        assets.story.waves.push("wave99".to_string());
+        assets.sounds.retain(|sound| sound != "demon_summon_sting");
+        let problems = check_assets(&assets);
+        assert!(problems.contains(&AssetProblem {
+            asset: "summons/Bane.summon".to_string(),
+            message: "brain \"demon\" does not exist".to_string(),
+        }));
+        assert!(problems.contains(&AssetProblem {
+            asset: "brains/reckless.brain".to_string(),
+            message:
+                "Sequence > AttackTarget#1: needs PickValidAttack or PickRandomAttack before it"
+                    .to_string(),
+        }));
+        assert!(problems.contains(&AssetProblem {
+            asset: "brains/lost.brain".to_string(),
+            message: IncludeError::Missing("demon".to_string()).to_string(),
+        }));
+        assert!(problems.contains(&AssetProblem {
+            asset: "story.teller".to_string(),
+            message: "wave \"wave99\" does not exist".to_string(),
+        }));
+        assert!(problems.contains(&AssetProblem {
+            asset: "fused/Inferno.summon".to_string(),
+            message: "sound \"demon_summon_sting\" is not in sounds/".to_string(),
+        }));
+        assert!(problems.contains(&AssetProblem {
+            asset: CONTENT_MANIFEST.to_string(),
+            message: "Demon needs \"sounds/demon_summon_sting.wav\"".to_string(),
+        }));
+        assert!(problems.contains(&AssetProblem {
+            asset: "story.teller".to_string(),
+            message: "losing_beats has 11 entries for 12 waves, expected 12".to_string(),
+        }));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlackboardValue {
+    Flag(bool),
+    Target(RememberedTarget),
+}
+
+// Enough of a unit to tell whether it's still the one on its tile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RememberedTarget {
+    pub location: (usize, usize),
+    pub enemy: bool,
+    pub name: String,
+}
+
+// Survives between turns, unlike the rest of the controller.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Blackboard {
+    values: HashMap<String, BlackboardValue>,
+    turns_taken: usize,
+}
+
+impl Blackboard {
+    pub fn turns_taken(&self) -> usize {
+        self.turns_taken
+    }
+
+    pub fn end_turn(&mut self) {
+        self.turns_taken += 1;
+    }
+
+    pub fn get_flag(&self, key: &str) -> bool {
+        match self.values.get(key) {
+            Some(BlackboardValue::Flag(flag)) => *flag,
+            _ => false,
+        }
+    }
+
+    pub fn set_flag(&mut self, key: &str, flag: bool) {
+        self.values
+            .insert(key.to_string(), BlackboardValue::Flag(flag));
+    }
+
+    pub fn get_target(&self, key: &str) -> Option<&RememberedTarget> {
+        match self.values.get(key) {
+            Some(BlackboardValue::Target(target)) => Some(target),
+            _ => None,
+        }
+    }
+
+    pub fn set_target(&mut self, key: &str, target: RememberedTarget) {
+        self.values
+            .insert(key.to_string(), BlackboardValue::Target(target));
+    }
+}
@@ -1,4 +1,12 @@
-use crate::prelude::*;
+use std::{borrow::Borrow, cmp::Reverse};
+
+use bevy::utils::HashSet;
+
+use crate::{prelude::*, summons::Tribe};
+
+use super::{validate_brain, RememberedTarget};
+
+const BOARD_TILES: usize = 8;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BehaviorModel {
@@ -6,9 +14,64 @@ pub struct BehaviorModel {
     pub stats: CharacterStats,
     pub enemies: Vec<(usize, usize)>,
     pub allies: Vec<(usize, usize)>,
+    pub unit_stats: HashMap<(usize, usize), CharacterStats>,
 }
 
 impl BehaviorModel {
+    // What a fighter sees of everyone on the board. Tiles with a dead unit on them are left out.
+    pub fn for_fighter(
+        position: (usize, usize),
+        stats: &CharacterStats,
+        faction: &Faction,
+        everyone: &[(Faction, (usize, usize), CharacterStats)],
+    ) -> Self {
+        let dead: HashSet<_> = everyone
+            .iter()
+            .filter(|(_, _, stats)| stats.health <= 0)
+            .map(|(_, location, _)| *location)
+            .collect();
+        let living = |allied: bool| {
+            everyone
+                .iter()
+                .filter(|(other, location, _)| {
+                    (other == faction) == allied && !dead.contains(location)
+                })
+                .map(|(_, location, _)| *location)
+                .collect()
+        };
+        BehaviorModel {
+            position,
+            stats: stats.clone(),
+            enemies: living(false),
+            allies: living(true),
+            unit_stats: everyone
+                .iter()
+                .filter(|(_, _, stats)| stats.health > 0)
+                .map(|(_, location, stats)| (*location, stats.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn distance_to(&self, (x, y): (usize, usize)) -> i32 {
+        let dx = x as i32 - self.position.0 as i32;
+        let dy = y as i32 - self.position.1 as i32;
+        dx.abs() + dy.abs()
+    }
+
+    pub fn enemies_in_range(&self, range: i32) -> usize {
+        self.enemies
+            .iter()
+            .filter(|enemy| self.distance_to(**enemy) <= range)
+            .count()
+    }
+
+    pub fn allies_in_range(&self, range: i32) -> usize {
+        self.allies
+            .iter()
+            .filter(|ally| **ally != self.position && self.distance_to(**ally) <= range)
+            .count()
+    }
+
     pub fn find_nearest_enemy(&self) -> Option<(usize, usize)> {
         self.enemies
             .iter()
@@ -34,6 +97,120 @@ impl BehaviorModel {
     pub fn location_occupied(&self, x: usize, y: usize) -> bool {
         self.enemies.contains(&(x, y)) || self.allies.contains(&(x, y))
     }
+
+    pub fn stats_at(&self, location: (usize, usize)) -> Option<&CharacterStats> {
+        self.unit_stats.get(&location)
+    }
+
+    // Who stands on the tile now, as RememberTarget would store them.
+    pub fn target_at(&self, location: (usize, usize)) -> Option<RememberedTarget> {
+        let enemy = self.enemies.contains(&location);
+        if !enemy && !self.allies.contains(&location) {
+            return None;
+        }
+        Some(RememberedTarget {
+            location,
+            enemy,
+            name: self
+                .stats_at(location)
+                .map(|stats| stats.name.clone())
+                .unwrap_or_default(),
+        })
+    }
+
+    // The enemy with the highest key, skipping those keyed None. Ties go to the nearest.
+    fn best_enemy_by<K: Ord>(
+        &self,
+        key: impl Fn((usize, usize), &CharacterStats) -> Option<K>,
+    ) -> Option<(usize, usize)> {
+        self.enemies
+            .iter()
+            .filter_map(|enemy| {
+                let stats = self.stats_at(*enemy)?;
+                Some((
+                    key(*enemy, stats)?,
+                    Reverse(self.distance_to(*enemy)),
+                    *enemy,
+                ))
+            })
+            .max()
+            .map(|(_, _, enemy)| enemy)
+    }
+
+    pub fn find_weakest_enemy(&self) -> Option<(usize, usize)> {
+        self.best_enemy_by(|_, stats| Some(Reverse(stats.health)))
+    }
+
+    pub fn find_strongest_enemy(&self) -> Option<(usize, usize)> {
+        self.best_enemy_by(|_, stats| Some(stats.max_damage()))
+    }
+
+    pub fn find_most_threatening_enemy(&self) -> Option<(usize, usize)> {
+        self.best_enemy_by(|enemy, _| Some(self.threat_of(enemy)))
+    }
+
+    pub fn find_enemy_of_tribe(&self, tribe: &Tribe) -> Option<(usize, usize)> {
+        self.best_enemy_by(|_, stats| (stats.tribe == *tribe).then_some(()))
+    }
+
+    // The damage an enemy can deal, counted once for every ally (including us) it can reach.
+    pub fn threat_of(&self, (x, y): (usize, usize)) -> i32 {
+        let Some(stats) = self.stats_at((x, y)) else {
+            return 0;
+        };
+        self.allies
+            .iter()
+            .map(|(a_x, a_y)| {
+                let distance = (x as i32 - *a_x as i32).abs() + (y as i32 - *a_y as i32).abs();
+                stats
+                    .attacks
+                    .iter()
+                    .filter(|attack| distance <= attack.range)
+                    .map(|attack| attack.damage)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .sum()
+    }
+
+    // Empty tiles next to a location, counting our own tile as empty.
+    pub fn open_neighbors(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbors = vec![];
+        if x > 0 {
+            neighbors.push((x - 1, y));
+        }
+        if x < BOARD_TILES - 1 {
+            neighbors.push((x + 1, y));
+        }
+        if y > 0 {
+            neighbors.push((x, y - 1));
+        }
+        if y < BOARD_TILES - 1 {
+            neighbors.push((x, y + 1));
+        }
+        neighbors.retain(|(n_x, n_y)| {
+            (*n_x, *n_y) == self.position || !self.location_occupied(*n_x, *n_y)
+        });
+        neighbors
+    }
+
+    // The nearest ally one of our friendly auras would apply to, but isn't yet.
+    pub fn find_ally_needing_aura(&self) -> Option<((usize, usize), AuraEffect)> {
+        let mut allies = self.allies.clone();
+        allies.sort_by_key(|ally| self.distance_to(*ally));
+        allies.into_iter().find_map(|ally| {
+            let stats = self.stats_at(ally)?;
+            self.stats
+                .auras
+                .iter()
+                .find(|aura| {
+                    aura.is_friendly()
+                        && aura.applies_to(&stats.tribe)
+                        && !stats.applied_auras.contains(aura)
+                })
+                .map(|aura| (ally, aura.clone()))
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,9 +235,24 @@ pub struct BehaviorController {
     pub picked_location: Option<(usize, usize)>,
     pub picked_index: Option<usize>,
     pub picked_aura: Option<AuraEffect>,
+    pub blackboard: Blackboard,
+    pub rng: StdRng,
+    pub trace: Option<UnpoweredTrace>,
 }
 
 impl BehaviorController {
+    pub fn new(blackboard: Blackboard, seed: u64) -> Self {
+        BehaviorController {
+            actions: vec![],
+            picked_location: None,
+            picked_index: None,
+            picked_aura: None,
+            blackboard,
+            rng: StdRng::seed_from_u64(seed),
+            trace: None,
+        }
+    }
+
     pub fn remaining_stamina(&self, model: &BehaviorModel) -> i32 {
         let mut stamina = model.stats.stamina as i32;
         for action in &self.actions {
@@ -74,23 +266,183 @@ impl BehaviorController {
     }
 }
 
+impl UnpoweredRandom for BehaviorController {
+    fn random_below(self: &mut Self, range: u32) -> u32 {
+        self.rng.gen_range(0..range)
+    }
+}
+
+impl UnpoweredClock for BehaviorController {
+    fn current_turn(self: &Self) -> usize {
+        self.blackboard.turns_taken()
+    }
+}
+
+impl UnpoweredTracer for BehaviorController {
+    fn trace_mut(self: &mut Self) -> Option<&mut UnpoweredTrace> {
+        self.trace.as_mut()
+    }
+}
+
 pub type CharacterBrainNode =
     dyn UnpoweredFunction<Model = BehaviorModel, Controller = BehaviorController> + Send + Sync;
-pub type CharacterBrainDef = UnpoweredTreeDef<SummonBehaviors, SummonWrapperDef>;
+pub type CharacterBrainDef = UnpoweredTreeDef<SummonBehaviors, SummonWrapperDef, SummonScorer>;
 
 #[derive(Component)]
 pub struct CharacterBrain {
     pub tree: Box<CharacterBrainNode>,
+    pub blackboard: Blackboard,
+    pub last_trace: Option<UnpoweredTrace>,
+    // The .brain asset the tree was built from, so edits to it can be reloaded.
+    pub source: AssetId<CharacterBrainDef>,
+    // Set while the tree is Waiting, so it resumes with the same picks next turn.
+    pub held_picks: Option<HeldPicks>,
 }
 
 #[derive(Component)]
 pub struct DeathCharacterBrain(pub CharacterBrain);
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeldPicks {
+    pub location: Option<(usize, usize)>,
+    // Who stood on the location when the hold began.
+    pub target: Option<RememberedTarget>,
+    pub index: Option<usize>,
+    pub aura: Option<AuraEffect>,
+}
+
 impl CharacterBrain {
-    pub fn new(tree_def: &CharacterBrainDef) -> Self {
-        let tree = tree_def.create_tree();
-        CharacterBrain { tree }
+    // Fails when tree_def still has an Include, so pass it through resolve_brain first.
+    pub fn new(
+        source: AssetId<CharacterBrainDef>,
+        tree_def: &CharacterBrainDef,
+    ) -> Result<Self, IncludeError> {
+        let tree = tree_def.create_tree()?;
+        Ok(CharacterBrain {
+            tree,
+            blackboard: Blackboard::default(),
+            last_trace: None,
+            source,
+            held_picks: None,
+        })
+    }
+
+    pub fn is_winding_up(&self) -> bool {
+        self.held_picks.is_some()
+    }
+
+    // Swaps in a fresh tree, but the unit remembers everything it learned. A wind-up is
+    // dropped, since its picks were made by the old tree.
+    pub fn rebuild(&mut self, tree_def: &CharacterBrainDef) -> Result<(), IncludeError> {
+        self.tree = tree_def.create_tree()?;
+        self.held_picks = None;
+        Ok(())
+    }
+
+    pub fn think(&mut self, model: &BehaviorModel, seed: u64, trace: bool) -> BehaviorController {
+        let mut controller = BehaviorController::new(std::mem::take(&mut self.blackboard), seed);
+        controller.trace = trace.then(UnpoweredTrace::default);
+        if let Some(held) = self.held_picks.take() {
+            // With someone else on the tile, even an ally, the picks are dropped and the
+            // wound-up node fails for lack of a target.
+            if held.location.and_then(|location| model.target_at(location)) == held.target {
+                controller.picked_location = held.location;
+                controller.picked_index = held.index;
+                controller.picked_aura = held.aura;
+            }
+        }
+        let result = self.tree.resume_with(model, &mut controller);
+        if result == UnpoweredFunctionState::Waiting {
+            self.held_picks = Some(HeldPicks {
+                location: controller.picked_location,
+                target: controller
+                    .picked_location
+                    .and_then(|location| model.target_at(location)),
+                index: controller.picked_index,
+                aura: controller.picked_aura.clone(),
+            });
+        }
+        controller.blackboard.end_turn();
+        self.blackboard = std::mem::take(&mut controller.blackboard);
+        self.last_trace = controller.trace.take();
+        controller
+    }
+}
+
+// Looks up the brain and inlines every brain it includes.
+pub fn resolve_brain(
+    source: AssetId<CharacterBrainDef>,
+    brains: &BrainAssets,
+    brain_defs: &Assets<CharacterBrainDef>,
+) -> Result<CharacterBrainDef, IncludeError> {
+    let name = brain_name(source, brains);
+    let find = |name: &str| {
+        brains
+            .brains
+            .get(name)
+            .and_then(|handle| brain_defs.get(handle))
+    };
+    brain_defs
+        .get(source)
+        .ok_or_else(|| IncludeError::Missing(name.clone()))?
+        .resolve_includes(&name, &find)
+}
+
+// The brain's file stem, or empty if it isn't one of the loaded brains.
+pub fn brain_name(source: AssetId<CharacterBrainDef>, brains: &BrainAssets) -> String {
+    brains
+        .brains
+        .iter()
+        .find(|(_, handle)| handle.id() == source)
+        .map(|(name, _)| Borrow::<str>::borrow(name).to_string())
+        .unwrap_or_default()
+}
+
+// Asset events only fire for edits on disk with bevy's file_watcher feature:
+// cargo run --features bevy/file_watcher
+pub fn reload_changed_brains(
+    mut events: EventReader<AssetEvent<CharacterBrainDef>>,
+    brains: Option<Res<BrainAssets>>,
+    brain_defs: Res<Assets<CharacterBrainDef>>,
+    mut units: Query<(&mut CharacterBrain, &mut DeathCharacterBrain)>,
+) {
+    let Some(brains) = brains else {
+        return;
+    };
+    let mut changed = false;
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event {
+            match resolve_brain(*id, &brains, &brain_defs) {
+                Ok(brain_def) => {
+                    for warning in validate_brain(&brain_def) {
+                        warn!("Reloaded brain: {}", warning);
+                    }
+                }
+                Err(err) => warn!("Reloaded brain: {}", err),
+            }
+            changed = true;
+        }
+    }
+    if !changed {
+        return;
+    }
+    // Any brain may include the one that changed, so rebuild them all.
+    let mut reloaded = 0;
+    for (mut brain, mut death_brain) in units.iter_mut() {
+        for brain in [brain.as_mut(), &mut death_brain.0] {
+            match resolve_brain(brain.source, &brains, &brain_defs)
+                .and_then(|brain_def| brain.rebuild(&brain_def))
+            {
+                Ok(()) => reloaded += 1,
+                Err(err) => warn!(
+                    "brains/{}.brain: a unit kept its old tree: {}",
+                    brain_name(brain.source, &brains),
+                    err
+                ),
+            }
+        }
     }
+    info!("Reloaded {} living brains", reloaded);
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TypePath)]
@@ -99,6 +451,12 @@ pub enum SummonBehaviors {
     FindNearestAlly,
     FindRandomEnemy,
     FindRandomAlly,
+    FindWeakestEnemy,
+    FindStrongestEnemy,
+    FindMostThreateningEnemy,
+    FindEnemyOfTribe(Tribe),
+    // Picks the aura too, so it can be followed by RefreshAuraForTarget.
+    FindAllyNeedingAura,
     PickValidAttack,
     PickRandomAttack,
     PickValidMovement,
@@ -112,6 +470,27 @@ pub enum SummonBehaviors {
     MoveTowardsTarget,
     MoveAwayFromTarget,
     RefreshAuraForTarget,
+    SetFlag(String, bool),
+    CheckFlag(String),
+    RememberTarget(String),
+    RecallTarget(String),
+    HealthBelowPercent(i32),
+    HealthAbovePercent(i32),
+    StaminaAtLeast(i32),
+    EnemiesInRange(i32, usize),
+    AlliesInRange(i32, usize),
+    TargetIsTribe(Tribe),
+    // Percent of the target's max health.
+    TargetHealthBelow(i32),
+    IsFirstTurn,
+    // Moves towards or away from the target until it is this many tiles away.
+    KeepDistance(i32),
+    // Moves next to the nearest ally of the tribe, leaving the target alone.
+    StayAdjacentToAlly(Tribe),
+    // Moves next to the target, on the side furthest from our other allies.
+    Flank,
+    // Completes without moving, so later movement is skipped this turn.
+    HoldPosition,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TypePath)]
@@ -217,6 +596,54 @@ impl UserWrapperDefinition<SummonBehaviors> for SummonWrapperDef {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TypePath)]
+pub enum SummonScorer {
+    Constant(f32),
+    HealthRatio,
+    NearestEnemyDistance,
+    TargetDistance,
+    EnemiesInRange(i32),
+    EnemiesInAttackRange,
+    AlliesInRange(i32),
+    Scaled(f32, Box<SummonScorer>),
+    Sum(Vec<SummonScorer>),
+}
+
+impl UtilityScorer for SummonScorer {
+    type Model = BehaviorModel;
+    type Controller = BehaviorController;
+
+    fn score(self: &Self, model: &Self::Model, controller: &Self::Controller) -> f32 {
+        match self {
+            SummonScorer::Constant(score) => *score,
+            SummonScorer::HealthRatio => {
+                model.stats.health as f32 / model.stats.max_health.max(1) as f32
+            }
+            SummonScorer::NearestEnemyDistance => model
+                .find_nearest_enemy()
+                .map(|enemy| model.distance_to(enemy) as f32)
+                .unwrap_or(0.),
+            SummonScorer::TargetDistance => controller
+                .picked_location
+                .map(|target| model.distance_to(target) as f32)
+                .unwrap_or(0.),
+            SummonScorer::EnemiesInRange(range) => model.enemies_in_range(*range) as f32,
+            SummonScorer::EnemiesInAttackRange => {
+                let range = model.stats.attacks.iter().map(|a| a.range).max();
+                range
+                    .map(|range| model.enemies_in_range(range) as f32)
+                    .unwrap_or(0.)
+            }
+            SummonScorer::AlliesInRange(range) => model.allies_in_range(*range) as f32,
+            SummonScorer::Scaled(scale, scorer) => scale * scorer.score(model, controller),
+            SummonScorer::Sum(scorers) => scorers
+                .iter()
+                .map(|scorer| scorer.score(model, controller))
+                .sum(),
+        }
+    }
+}
+
 impl UnpoweredFunction for SummonBehaviors {
     type Model = BehaviorModel;
     type Controller = BehaviorController;
@@ -244,7 +671,7 @@ impl UnpoweredFunction for SummonBehaviors {
                 }
             }
             SummonBehaviors::FindRandomEnemy => {
-                if let Some(enemy) = model.enemies.choose(&mut rand::thread_rng()) {
+                if let Some(enemy) = model.enemies.choose(&mut controller.rng) {
                     controller.picked_location = Some(*enemy);
                     UnpoweredFunctionState::Complete
                 } else {
@@ -252,13 +679,34 @@ impl UnpoweredFunction for SummonBehaviors {
                 }
             }
             SummonBehaviors::FindRandomAlly => {
-                if let Some(ally) = model.allies.choose(&mut rand::thread_rng()) {
+                if let Some(ally) = model.allies.choose(&mut controller.rng) {
                     controller.picked_location = Some(*ally);
                     UnpoweredFunctionState::Complete
                 } else {
                     UnpoweredFunctionState::Failed
                 }
             }
+            SummonBehaviors::FindWeakestEnemy => {
+                pick_target(controller, model.find_weakest_enemy())
+            }
+            SummonBehaviors::FindStrongestEnemy => {
+                pick_target(controller, model.find_strongest_enemy())
+            }
+            SummonBehaviors::FindMostThreateningEnemy => {
+                pick_target(controller, model.find_most_threatening_enemy())
+            }
+            SummonBehaviors::FindEnemyOfTribe(tribe) => {
+                pick_target(controller, model.find_enemy_of_tribe(tribe))
+            }
+            SummonBehaviors::FindAllyNeedingAura => {
+                if let Some((ally, aura)) = model.find_ally_needing_aura() {
+                    controller.picked_location = Some(ally);
+                    controller.picked_aura = Some(aura);
+                    UnpoweredFunctionState::Complete
+                } else {
+                    UnpoweredFunctionState::Failed
+                }
+            }
             SummonBehaviors::PickValidAttack => {
                 if let Some((x, y)) = controller.picked_location {
                     let dx = x as i32 - model.position.0 as i32;
@@ -286,7 +734,7 @@ impl UnpoweredFunction for SummonBehaviors {
                             valid_attacks.push(index);
                         }
                     }
-                    if let Some(index) = valid_attacks.choose(&mut rand::thread_rng()) {
+                    if let Some(index) = valid_attacks.choose(&mut controller.rng) {
                         controller.picked_index = Some(*index);
                         return UnpoweredFunctionState::Complete;
                     }
@@ -318,7 +766,7 @@ impl UnpoweredFunction for SummonBehaviors {
                             valid_moves.push(index);
                         }
                     }
-                    if let Some(index) = valid_moves.choose(&mut rand::thread_rng()) {
+                    if let Some(index) = valid_moves.choose(&mut controller.rng) {
                         controller.picked_index = Some(*index);
                         return UnpoweredFunctionState::Complete;
                     }
@@ -332,7 +780,7 @@ impl UnpoweredFunction for SummonBehaviors {
                 UnpoweredFunctionState::Complete
             }
             SummonBehaviors::PickRandomAura => {
-                if let Some(aura) = model.stats.auras.choose(&mut rand::thread_rng()) {
+                if let Some(aura) = model.stats.auras.choose(&mut controller.rng) {
                     controller.picked_aura = Some(aura.clone());
                     UnpoweredFunctionState::Complete
                 } else {
@@ -346,7 +794,7 @@ impl UnpoweredFunction for SummonBehaviors {
                     .iter()
                     .filter(|aura| aura.is_friendly())
                     .collect::<Vec<_>>()
-                    .choose(&mut rand::thread_rng())
+                    .choose(&mut controller.rng)
                 {
                     controller.picked_aura = Some(aura.to_owned().clone());
                     UnpoweredFunctionState::Complete
@@ -361,7 +809,7 @@ impl UnpoweredFunction for SummonBehaviors {
                     .iter()
                     .filter(|aura| !aura.is_friendly())
                     .collect::<Vec<_>>()
-                    .choose(&mut rand::thread_rng())
+                    .choose(&mut controller.rng)
                 {
                     controller.picked_aura = Some(aura.to_owned().clone());
                     UnpoweredFunctionState::Complete
@@ -451,12 +899,223 @@ impl UnpoweredFunction for SummonBehaviors {
                     UnpoweredFunctionState::Failed
                 }
             }
+            SummonBehaviors::SetFlag(key, flag) => {
+                controller.blackboard.set_flag(key, *flag);
+                UnpoweredFunctionState::Complete
+            }
+            SummonBehaviors::CheckFlag(key) => {
+                if controller.blackboard.get_flag(key) {
+                    UnpoweredFunctionState::Complete
+                } else {
+                    UnpoweredFunctionState::Failed
+                }
+            }
+            SummonBehaviors::RememberTarget(key) => {
+                if let Some(target) = controller
+                    .picked_location
+                    .and_then(|location| model.target_at(location))
+                {
+                    controller.blackboard.set_target(key, target);
+                    UnpoweredFunctionState::Complete
+                } else {
+                    UnpoweredFunctionState::Failed
+                }
+            }
+            SummonBehaviors::RecallTarget(key) => {
+                // The remembered unit may have moved or died since, and someone else,
+                // even an ally, may stand on its tile now.
+                if let Some(target) = controller
+                    .blackboard
+                    .get_target(key)
+                    .filter(|target| model.target_at(target.location).as_ref() == Some(*target))
+                {
+                    controller.picked_location = Some(target.location);
+                    UnpoweredFunctionState::Complete
+                } else {
+                    UnpoweredFunctionState::Failed
+                }
+            }
+            SummonBehaviors::HealthBelowPercent(percent) => {
+                check(model.stats.health * 100 < model.stats.max_health * *percent)
+            }
+            SummonBehaviors::HealthAbovePercent(percent) => {
+                check(model.stats.health * 100 > model.stats.max_health * *percent)
+            }
+            SummonBehaviors::StaminaAtLeast(stamina) => {
+                check(controller.remaining_stamina(model) >= *stamina)
+            }
+            SummonBehaviors::EnemiesInRange(range, count) => {
+                check(model.enemies_in_range(*range) >= *count)
+            }
+            SummonBehaviors::AlliesInRange(range, count) => {
+                check(model.allies_in_range(*range) >= *count)
+            }
+            SummonBehaviors::TargetIsTribe(tribe) => check(
+                controller
+                    .picked_location
+                    .and_then(|location| model.stats_at(location))
+                    .map(|target| target.tribe == *tribe)
+                    .unwrap_or(false),
+            ),
+            SummonBehaviors::TargetHealthBelow(percent) => check(
+                controller
+                    .picked_location
+                    .and_then(|location| model.stats_at(location))
+                    .map(|target| target.health * 100 < target.max_health * *percent)
+                    .unwrap_or(false),
+            ),
+            SummonBehaviors::IsFirstTurn => check(controller.blackboard.turns_taken() == 0),
+            SummonBehaviors::KeepDistance(range) => {
+                let (Some(target), Some(movement)) = (
+                    controller.picked_location,
+                    picked_movement(model, controller),
+                ) else {
+                    return UnpoweredFunctionState::Failed;
+                };
+                let distance = model.distance_to(target);
+                let mut location = model.position;
+                for _ in 0..movement.tiles.min((distance - *range).abs()) {
+                    location = if distance > *range {
+                        movement.next_location(location.0, location.1, target.0, target.1)
+                    } else if let Some(away) = step_away(location, target) {
+                        away
+                    } else {
+                        break;
+                    };
+                }
+                move_to(model, controller, movement, location);
+                UnpoweredFunctionState::Complete
+            }
+            SummonBehaviors::StayAdjacentToAlly(tribe) => {
+                let Some(movement) = picked_movement(model, controller) else {
+                    return UnpoweredFunctionState::Failed;
+                };
+                let Some(ally) = model
+                    .allies
+                    .iter()
+                    .filter(|ally| **ally != model.position)
+                    .filter(|ally| {
+                        model
+                            .stats_at(**ally)
+                            .map(|stats| stats.tribe == *tribe)
+                            .unwrap_or(false)
+                    })
+                    .min_by_key(|ally| model.distance_to(**ally))
+                    .cloned()
+                else {
+                    return UnpoweredFunctionState::Failed;
+                };
+                if let Some(spot) = model
+                    .open_neighbors(ally)
+                    .into_iter()
+                    .min_by_key(|spot| model.distance_to(*spot))
+                {
+                    move_to(model, controller, movement, spot);
+                }
+                UnpoweredFunctionState::Complete
+            }
+            SummonBehaviors::Flank => {
+                let (Some(target), Some(movement)) = (
+                    controller.picked_location,
+                    picked_movement(model, controller),
+                ) else {
+                    return UnpoweredFunctionState::Failed;
+                };
+                let others: Vec<_> = model
+                    .allies
+                    .iter()
+                    .filter(|ally| **ally != model.position)
+                    .collect();
+                let from_others = |(x, y): (usize, usize)| -> i32 {
+                    others
+                        .iter()
+                        .map(|(a_x, a_y)| {
+                            let dx = x as i32 - *a_x as i32;
+                            let dy = y as i32 - *a_y as i32;
+                            dx * dx + dy * dy
+                        })
+                        .sum()
+                };
+                if let Some(spot) = model
+                    .open_neighbors(target)
+                    .into_iter()
+                    .max_by_key(|spot| (from_others(*spot), Reverse(model.distance_to(*spot))))
+                {
+                    move_to(model, controller, movement, spot);
+                    UnpoweredFunctionState::Complete
+                } else {
+                    UnpoweredFunctionState::Failed
+                }
+            }
+            SummonBehaviors::HoldPosition => UnpoweredFunctionState::Complete,
         }
     }
 
     fn reset(self: &mut Self, _parameter: &Self::Model) {}
 }
 
+fn check(condition: bool) -> UnpoweredFunctionState {
+    if condition {
+        UnpoweredFunctionState::Complete
+    } else {
+        UnpoweredFunctionState::Failed
+    }
+}
+
+fn pick_target(
+    controller: &mut BehaviorController,
+    target: Option<(usize, usize)>,
+) -> UnpoweredFunctionState {
+    if let Some(target) = target {
+        controller.picked_location = Some(target);
+        UnpoweredFunctionState::Complete
+    } else {
+        UnpoweredFunctionState::Failed
+    }
+}
+
+fn picked_movement(model: &BehaviorModel, controller: &BehaviorController) -> Option<Movement> {
+    controller
+        .picked_index
+        .and_then(|index| model.stats.movements.get(index))
+        .cloned()
+}
+
+fn move_to(
+    model: &BehaviorModel,
+    controller: &mut BehaviorController,
+    movement: Movement,
+    location: (usize, usize),
+) {
+    if location != model.position {
+        controller.actions.push(Action::Move {
+            movement,
+            target: location,
+        });
+    }
+}
+
+// One tile further from the target along the longer axis, or the other axis at the board's edge.
+fn step_away((x, y): (usize, usize), (t_x, t_y): (usize, usize)) -> Option<(usize, usize)> {
+    let dx = x as i32 - t_x as i32;
+    let dy = y as i32 - t_y as i32;
+    let along_x = match dx.signum() {
+        1 if x < BOARD_TILES - 1 => Some((x + 1, y)),
+        -1 if x > 0 => Some((x - 1, y)),
+        _ => None,
+    };
+    let along_y = match dy.signum() {
+        1 if y < BOARD_TILES - 1 => Some((x, y + 1)),
+        -1 if y > 0 => Some((x, y - 1)),
+        _ => None,
+    };
+    if dx.abs() >= dy.abs() {
+        along_x.or(along_y)
+    } else {
+        along_y.or(along_x)
+    }
+}
+
 fn try_move_towards(
     model: &BehaviorModel,
     t_x: usize,
@@ -494,3 +1153,311 @@ fn try_move_towards(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(tribe: Tribe, health: i32, attack: Attack) -> CharacterStats {
+        CharacterStats {
+            max_health: 10,
+            is_dead: false,
+            health,
+            stamina: 10,
+            stamina_regen: 1,
+            name: "Tester".to_string(),
+            tribe,
+            attacks: vec![attack],
+            movements: vec![Movement::debug()],
+            auras: vec![],
+            applied_auras: vec![],
+            experience: 0,
+        }
+    }
+
+    fn model() -> BehaviorModel {
+        BehaviorModel {
+            position: (0, 0),
+            stats: stats(Tribe::Angel, 10, Attack::debug()),
+            enemies: vec![(3, 0)],
+            allies: vec![(0, 0)],
+            unit_stats: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn wind_up_resumes_with_held_picks() {
+        let def: CharacterBrainDef = ron::from_str(
+            "Sequence([User(FindNearestEnemy), User(PickValidAttack), WindUp(1, User(AttackTarget))])",
+        )
+        .unwrap();
+        let mut brain = CharacterBrain::new(AssetId::default(), &def).unwrap();
+        let mut model = model();
+        assert!(brain.think(&model, 0, false).actions.is_empty());
+        assert!(brain.is_winding_up());
+        // A fresh tree would go for the nearer enemy, so only a resumed tree attacks (3, 0).
+        model.enemies.push((1, 0));
+        let actions = brain.think(&model, 0, false).actions;
+        assert_eq!(
+            actions,
+            vec![Action::Attack {
+                attack: Attack::debug(),
+                target: (3, 0),
+            }]
+        );
+        assert!(!brain.is_winding_up());
+
+        // An ally stepping onto the tile drops the held picks, and the unit winds up anew.
+        model.enemies = vec![(3, 0)];
+        assert!(brain.think(&model, 0, false).actions.is_empty());
+        model.enemies = vec![(5, 0)];
+        model.allies.push((3, 0));
+        assert!(brain.think(&model, 0, false).actions.is_empty());
+        assert!(!brain.is_winding_up());
+        assert!(brain.think(&model, 0, false).actions.is_empty());
+        assert_eq!(
+            brain.held_picks.as_ref().and_then(|held| held.location),
+            Some((5, 0))
+        );
+        // A reloaded tree starts over rather than resuming the old tree's wind-up.
+        brain.rebuild(&def).unwrap();
+        assert!(!brain.is_winding_up());
+    }
+
+    #[test]
+    fn recalling_checks_who_is_on_the_tile() {
+        let def: CharacterBrainDef = ron::from_str(
+            "Selector([User(RecallTarget(\"prey\")), Sequence([User(FindNearestEnemy), User(RememberTarget(\"prey\"))])])",
+        )
+        .unwrap();
+        let recalled = |brain: &mut CharacterBrain, model: &BehaviorModel| {
+            brain.think(model, 0, false).picked_location
+        };
+        let mut brain = CharacterBrain::new(AssetId::default(), &def).unwrap();
+        let mut model = model();
+        model.unit_stats = HashMap::from([((3, 0), stats(Tribe::Enemy, 10, Attack::debug()))]);
+        assert_eq!(recalled(&mut brain, &model), Some((3, 0)));
+        assert_eq!(recalled(&mut brain, &model), Some((3, 0)));
+        // An ally stepped onto the tile, so the memory no longer points at the prey.
+        model.enemies = vec![];
+        model.allies = vec![(0, 0), (3, 0)];
+        assert_eq!(recalled(&mut brain, &model), None);
+    }
+
+    #[test]
+    fn conditions_check_the_model() {
+        let passes = |mut condition: SummonBehaviors,
+                      model: &BehaviorModel,
+                      controller: &mut BehaviorController| {
+            condition.resume_with(model, controller) == UnpoweredFunctionState::Complete
+        };
+        let mut model = model();
+        model.allies = vec![(0, 0), (0, 2)];
+        model.unit_stats = HashMap::from([((3, 0), stats(Tribe::Enemy, 4, Attack::debug()))]);
+        let mut controller = BehaviorController::new(Blackboard::default(), 0);
+
+        assert!(!passes(
+            SummonBehaviors::HealthBelowPercent(50),
+            &model,
+            &mut controller
+        ));
+        assert!(passes(
+            SummonBehaviors::HealthAbovePercent(50),
+            &model,
+            &mut controller
+        ));
+        model.stats.health = 4;
+        assert!(passes(
+            SummonBehaviors::HealthBelowPercent(50),
+            &model,
+            &mut controller
+        ));
+        assert!(!passes(
+            SummonBehaviors::HealthAbovePercent(50),
+            &model,
+            &mut controller
+        ));
+
+        assert!(passes(
+            SummonBehaviors::StaminaAtLeast(10),
+            &model,
+            &mut controller
+        ));
+        // Actions already taken this turn spend stamina.
+        controller.actions.push(Action::Attack {
+            attack: Attack::debug(),
+            target: (3, 0),
+        });
+        assert!(!passes(
+            SummonBehaviors::StaminaAtLeast(10),
+            &model,
+            &mut controller
+        ));
+
+        assert!(passes(
+            SummonBehaviors::EnemiesInRange(3, 1),
+            &model,
+            &mut controller
+        ));
+        assert!(!passes(
+            SummonBehaviors::EnemiesInRange(2, 1),
+            &model,
+            &mut controller
+        ));
+        // The unit itself doesn't count as an ally.
+        assert!(passes(
+            SummonBehaviors::AlliesInRange(2, 1),
+            &model,
+            &mut controller
+        ));
+        assert!(!passes(
+            SummonBehaviors::AlliesInRange(2, 2),
+            &model,
+            &mut controller
+        ));
+
+        // Target conditions fail until something is picked.
+        let enemy_tribe = || SummonBehaviors::TargetIsTribe(Tribe::Enemy);
+        assert!(!passes(enemy_tribe(), &model, &mut controller));
+        assert!(!passes(
+            SummonBehaviors::TargetHealthBelow(50),
+            &model,
+            &mut controller
+        ));
+        controller.picked_location = Some((3, 0));
+        assert!(passes(enemy_tribe(), &model, &mut controller));
+        assert!(!passes(
+            SummonBehaviors::TargetIsTribe(Tribe::Demon),
+            &model,
+            &mut controller
+        ));
+        assert!(passes(
+            SummonBehaviors::TargetHealthBelow(50),
+            &model,
+            &mut controller
+        ));
+        assert!(!passes(
+            SummonBehaviors::TargetHealthBelow(40),
+            &model,
+            &mut controller
+        ));
+
+        assert!(passes(
+            SummonBehaviors::IsFirstTurn,
+            &model,
+            &mut controller
+        ));
+        controller.blackboard.end_turn();
+        assert!(!passes(
+            SummonBehaviors::IsFirstTurn,
+            &model,
+            &mut controller
+        ));
+    }
+
+    #[test]
+    fn targeting_uses_unit_stats() {
+        let mut model = model();
+        let weak_brawler = Attack {
+            damage: 5,
+            range: 0,
+            stamina_cost: 1,
+        };
+        let archer = Attack {
+            damage: 3,
+            range: 10,
+            stamina_cost: 1,
+        };
+        model.enemies = vec![(1, 0), (5, 0), (0, 5)];
+        model.unit_stats = HashMap::from([
+            ((1, 0), stats(Tribe::Enemy, 2, weak_brawler)),
+            ((5, 0), stats(Tribe::Enemy, 8, Attack::debug())),
+            ((0, 5), stats(Tribe::Demon, 5, archer)),
+        ]);
+        assert_eq!(model.find_weakest_enemy(), Some((1, 0)));
+        assert_eq!(model.find_strongest_enemy(), Some((1, 0)));
+        // The brawler can't reach anyone, but the debug attack reaches across the board.
+        assert_eq!(model.find_most_threatening_enemy(), Some((0, 5)));
+        assert_eq!(model.find_enemy_of_tribe(&Tribe::Demon), Some((0, 5)));
+        assert_eq!(model.find_enemy_of_tribe(&Tribe::Undead), None);
+    }
+
+    #[test]
+    fn ally_needing_aura_skips_applied_and_other_tribes() {
+        let blessing = AuraEffect::Attack("Blessing".to_string(), 1, vec![Tribe::Angel]);
+        let mut model = model();
+        model.stats.auras = vec![blessing.clone()];
+        let mut blessed = stats(Tribe::Angel, 10, Attack::debug());
+        blessed.applied_auras = vec![blessing.clone()];
+        model.allies = vec![(1, 0), (2, 0), (3, 0)];
+        model.unit_stats = HashMap::from([
+            ((1, 0), blessed),
+            ((2, 0), stats(Tribe::Undead, 10, Attack::debug())),
+            ((3, 0), stats(Tribe::Angel, 10, Attack::debug())),
+        ]);
+        let mut brain = CharacterBrain::new(
+            AssetId::default(),
+            &ron::from_str("Sequence([User(FindAllyNeedingAura), User(RefreshAuraForTarget)])")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            brain.think(&model, 0, false).actions,
+            vec![Action::Aura {
+                effect: blessing,
+                target: (3, 0),
+            }]
+        );
+    }
+
+    fn moves(brain: &str, model: &BehaviorModel) -> Vec<(usize, usize)> {
+        let mut brain =
+            CharacterBrain::new(AssetId::default(), &ron::from_str(brain).unwrap()).unwrap();
+        brain
+            .think(model, 0, false)
+            .actions
+            .into_iter()
+            .filter_map(|action| match action {
+                Action::Move { target, .. } => Some(target),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keep_distance_kites_and_closes() {
+        let brain =
+            "Sequence([User(FindNearestEnemy), User(PickValidMovement), User(KeepDistance(3))])";
+        let mut model = model();
+        model.position = (2, 2);
+        model.allies = vec![(2, 2)];
+        model.enemies = vec![(3, 2)];
+        assert_eq!(moves(brain, &model), vec![(1, 2)]);
+        model.enemies = vec![(7, 2)];
+        assert_eq!(moves(brain, &model), vec![(3, 2)]);
+        model.enemies = vec![(5, 2)];
+        assert_eq!(moves(brain, &model), vec![]);
+    }
+
+    #[test]
+    fn stay_adjacent_to_ally_of_tribe() {
+        let brain = "Sequence([User(FindNearestEnemy), User(PickValidMovement), User(StayAdjacentToAlly(Construct))])";
+        let mut model = model();
+        model.enemies = vec![(7, 7)];
+        model.allies = vec![(0, 0), (0, 3), (3, 0)];
+        model.unit_stats = HashMap::from([
+            ((0, 3), stats(Tribe::Angel, 10, Attack::debug())),
+            ((3, 0), stats(Tribe::Construct, 10, Attack::debug())),
+        ]);
+        assert_eq!(moves(brain, &model), vec![(2, 0)]);
+    }
+
+    #[test]
+    fn flank_approaches_away_from_allies() {
+        let brain = "Sequence([User(FindNearestEnemy), User(PickValidMovement), User(Flank)])";
+        let mut model = model();
+        model.enemies = vec![(4, 4)];
+        model.allies = vec![(0, 0), (4, 2)];
+        assert_eq!(moves(brain, &model), vec![(4, 5)]);
+    }
+}
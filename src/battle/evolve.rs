@@ -0,0 +1,508 @@
+use std::path::{Path, PathBuf};
+
+use crate::{board::PLAYER_ROWS, headless::HeadlessAssets, prelude::*, summons::Tribe};
+
+use super::{
+    children, simulate_battle, validate_brain, SimulatedOutcome, SimulatedUnit, SummonBehaviors,
+};
+
+const FLAG_KEYS: [&str; 2] = ["a", "b"];
+const TRIBES: [Tribe; 7] = [
+    Tribe::Angel,
+    Tribe::Undead,
+    Tribe::Fairy,
+    Tribe::Construct,
+    Tribe::Elemental,
+    Tribe::Demon,
+    Tribe::Enemy,
+];
+// Trees past this size lose a little fitness per node, so they don't bloat.
+const SIZE_ALLOWANCE: usize = 24;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvolveOptions {
+    pub assets: PathBuf,
+    pub out: PathBuf,
+    pub generations: usize,
+    pub population: usize,
+    // Battles per wave for every candidate.
+    pub trials: usize,
+    // How many of the best trees to write out.
+    pub keep: usize,
+    pub seed: u64,
+}
+
+impl Default for EvolveOptions {
+    fn default() -> Self {
+        Self {
+            assets: PathBuf::from("assets"),
+            out: PathBuf::from("evolved_brains"),
+            generations: 30,
+            population: 40,
+            trials: 2,
+            keep: 3,
+            seed: 0,
+        }
+    }
+}
+
+impl EvolveOptions {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = EvolveOptions::default();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{} needs a value", flag))?;
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| format!("{} needs a number, not {}", flag, value))
+            };
+            match flag.as_str() {
+                "--assets" => options.assets = PathBuf::from(&value),
+                "--out" => options.out = PathBuf::from(&value),
+                "--generations" => options.generations = number()? as usize,
+                "--population" => options.population = number()?.max(2) as usize,
+                "--trials" => options.trials = number()?.max(1) as usize,
+                "--keep" => options.keep = number()? as usize,
+                "--seed" => options.seed = number()?,
+                _ => return Err(format!("unknown option {}", flag)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+pub const EVOLVE_USAGE: &str = "usage: evolve [--assets DIR] [--out DIR] [--generations N] [--population N] [--trials N] [--keep N] [--seed N]";
+
+// Entry point for `cargo run -- evolve`, which runs without opening a window.
+pub fn run_evolve(args: impl Iterator<Item = String>) {
+    let options = match EvolveOptions::parse(args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, EVOLVE_USAGE);
+            std::process::exit(2);
+        }
+    };
+    let assets = match HeadlessAssets::load(&options.assets) {
+        Ok(assets) => assets,
+        Err(err) => {
+            eprintln!("Could not load assets: {}", err);
+            std::process::exit(1);
+        }
+    };
+    let best = evolve(&assets, &options, |generation, fitness| {
+        println!("Generation {}: best fitness {:.2}", generation, fitness);
+    });
+    if let Err(err) = write_brains(&options.out, &best) {
+        eprintln!("Could not write brains: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn write_brains(out: &Path, best: &[(f32, CharacterBrainDef)]) -> Result<(), String> {
+    std::fs::create_dir_all(out).map_err(|err| err.to_string())?;
+    for (rank, (fitness, brain)) in best.iter().enumerate() {
+        let path = out.join(format!("evolved_{}.brain", rank));
+        let source = ron::ser::to_string_pretty(brain, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        std::fs::write(&path, source).map_err(|err| format!("{:?}: {}", path, err))?;
+        println!("Wrote {:?} (fitness {:.2})", path, fitness);
+    }
+    Ok(())
+}
+
+// Evolves brains that play against every shipped wave, and returns the best.
+pub fn evolve(
+    assets: &HeadlessAssets,
+    options: &EvolveOptions,
+    mut report: impl FnMut(usize, f32),
+) -> Vec<(f32, CharacterBrainDef)> {
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let mut names: Vec<_> = assets.brains.keys().collect();
+    names.sort();
+    let mut population: Vec<CharacterBrainDef> = names
+        .into_iter()
+        .filter_map(|name| assets.brain(name).ok())
+        .take(options.population / 2)
+        .collect();
+    while population.len() < options.population {
+        population.push(random_tree(&mut rng, 3));
+    }
+    let mut scored = score_all(assets, options, population);
+    for generation in 0..options.generations {
+        report(generation, scored[0].0);
+        // The best two always survive unchanged.
+        let mut next: Vec<_> = scored
+            .iter()
+            .take(2)
+            .map(|(_, tree)| tree.clone())
+            .collect();
+        while next.len() < options.population {
+            let mut child = tournament(&scored, &mut rng).clone();
+            if rng.gen_bool(0.5) {
+                let donor = tournament(&scored, &mut rng);
+                crossover(&mut child, donor, &mut rng);
+            }
+            mutate(&mut child, &mut rng);
+            next.push(child);
+        }
+        scored = score_all(assets, options, next);
+    }
+    report(options.generations, scored[0].0);
+    scored.truncate(options.keep);
+    scored
+}
+
+fn score_all(
+    assets: &HeadlessAssets,
+    options: &EvolveOptions,
+    population: Vec<CharacterBrainDef>,
+) -> Vec<(f32, CharacterBrainDef)> {
+    let mut scored: Vec<_> = population
+        .into_iter()
+        .map(|tree| (fitness(assets, &tree, options), tree))
+        .collect();
+    scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    scored
+}
+
+// Wins count most, then how much health each side kept. Warnings and bloat cost a little.
+pub fn fitness(assets: &HeadlessAssets, tree: &CharacterBrainDef, options: &EvolveOptions) -> f32 {
+    let mut total = 0.;
+    for (index, (_, wave)) in assets.waves.iter().enumerate() {
+        for trial in 0..options.trials {
+            let seed = options.seed ^ ((index * options.trials + trial) as u64);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let Some(units) = wave_battle(assets, wave, tree, &mut rng) else {
+                continue;
+            };
+            let before = health(&units);
+            let outcome = simulate_battle(units, &mut rng);
+            total += score(&outcome, before);
+        }
+    }
+    let penalty = validate_brain(tree).len() as f32 * 0.5
+        + size(tree).saturating_sub(SIZE_ALLOWANCE) as f32 * 0.05;
+    total - penalty
+}
+
+fn health(units: &[SimulatedUnit]) -> (i32, i32) {
+    let side = |faction: Faction| {
+        units
+            .iter()
+            .filter(|unit| unit.faction == faction)
+            .map(|unit| unit.stats.max_health)
+            .sum::<i32>()
+            .max(1)
+    };
+    (side(Faction::Player), side(Faction::Enemy))
+}
+
+fn score(outcome: &SimulatedOutcome, (player, enemy): (i32, i32)) -> f32 {
+    let result = match outcome.winner {
+        Some(Faction::Player) => 2.,
+        Some(Faction::Enemy) => -2.,
+        None => 0.,
+    };
+    result + outcome.player_health as f32 / player as f32
+        - outcome.enemy_health as f32 / enemy as f32
+}
+
+// The shipped wave, thinking with its own brains, against an army of the same summons on the
+// player's side that thinks with the candidate tree.
+fn wave_battle(
+    assets: &HeadlessAssets,
+    wave: &SummonedMinions,
+    tree: &CharacterBrainDef,
+    rng: &mut StdRng,
+) -> Option<Vec<SimulatedUnit>> {
+    let spawns = wave
+        .iter()
+        .map(|(at, summon)| (*at, summon.clone()))
+        .collect();
+    let mut units = vec![];
+    for (summon, enemy, player) in battle_tiles(spawns, rng) {
+        units.push(assets.unit(&summon, Faction::Enemy, enemy, None).ok()?);
+        units.push(
+            assets
+                .unit(&summon, Faction::Player, player, Some(tree))
+                .ok()?,
+        );
+    }
+    Some(units)
+}
+
+// Where each wave unit and its player-side twin stand. The wave keeps the tiles its file gives
+// it, and a twin goes to the mirrored tile unless someone on either side already has it.
+fn battle_tiles(
+    mut spawns: Vec<((usize, usize), String)>,
+    rng: &mut StdRng,
+) -> Vec<(String, (usize, usize), (usize, usize))> {
+    spawns.sort();
+    let mut taken = vec![];
+    let mut enemies = vec![];
+    for ((mut x, mut y), summon) in spawns {
+        // Like SummonedMinions::normalize, but seeded.
+        while x >= 8 || y >= 8 || taken.contains(&(x, y)) {
+            x = rng.gen_range(0..8);
+            y = rng.gen_range(PLAYER_ROWS..8);
+        }
+        taken.push((x, y));
+        enemies.push((summon, (x, y)));
+    }
+    let mut tiles = vec![];
+    for (summon, (x, y)) in enemies {
+        let mut player = (7 - x, 7 - y);
+        while taken.contains(&player) {
+            player = (rng.gen_range(0..8), rng.gen_range(0..PLAYER_ROWS));
+        }
+        taken.push(player);
+        tiles.push((summon, (x, y), player));
+    }
+    tiles
+}
+
+fn tournament<'a>(
+    scored: &'a [(f32, CharacterBrainDef)],
+    rng: &mut StdRng,
+) -> &'a CharacterBrainDef {
+    (0..3)
+        .map(|_| rng.gen_range(0..scored.len()))
+        .min()
+        .map(|index| &scored[index].1)
+        .unwrap()
+}
+
+pub fn random_behavior(rng: &mut StdRng) -> SummonBehaviors {
+    use SummonBehaviors::*;
+    let key = FLAG_KEYS.choose(rng).unwrap().to_string();
+    let tribe = TRIBES.choose(rng).unwrap().clone();
+    let percent = rng.gen_range(1..10) * 10;
+    let range = rng.gen_range(1..5);
+    let count = rng.gen_range(1..4);
+    let behaviors = [
+        FindNearestEnemy,
+        FindNearestAlly,
+        FindRandomEnemy,
+        FindRandomAlly,
+        FindWeakestEnemy,
+        FindStrongestEnemy,
+        FindMostThreateningEnemy,
+        FindEnemyOfTribe(tribe.clone()),
+        FindAllyNeedingAura,
+        PickValidAttack,
+        PickRandomAttack,
+        PickValidMovement,
+        PickRandomMovement,
+        PickAura,
+        PickRandomAura,
+        PickFriendlyAura,
+        PickUnfriendlyAura,
+        CheckRange(range as usize),
+        AttackTarget,
+        MoveTowardsTarget,
+        MoveAwayFromTarget,
+        RefreshAuraForTarget,
+        SetFlag(key.clone(), rng.gen()),
+        CheckFlag(key.clone()),
+        RememberTarget(key.clone()),
+        RecallTarget(key),
+        HealthBelowPercent(percent),
+        HealthAbovePercent(percent),
+        StaminaAtLeast(range),
+        EnemiesInRange(range, count),
+        AlliesInRange(range, count),
+        TargetIsTribe(tribe.clone()),
+        TargetHealthBelow(percent),
+        IsFirstTurn,
+        KeepDistance(range),
+        StayAdjacentToAlly(tribe),
+        Flank,
+        HoldPosition,
+    ];
+    behaviors.choose(rng).unwrap().clone()
+}
+
+pub fn random_tree(rng: &mut StdRng, depth: usize) -> CharacterBrainDef {
+    if depth == 0 || rng.gen_bool(0.3) {
+        return UnpoweredTreeDef::User(random_behavior(rng));
+    }
+    let kind = rng.gen_range(0..10);
+    if kind == 9 {
+        return UnpoweredTreeDef::Succeeder(Box::new(random_tree(rng, depth - 1)));
+    }
+    let nodes = (0..rng.gen_range(2..=4))
+        .map(|_| random_tree(rng, depth - 1))
+        .collect();
+    match kind {
+        0..=3 => UnpoweredTreeDef::Sequence(nodes),
+        4..=7 => UnpoweredTreeDef::Selector(nodes),
+        _ => UnpoweredTreeDef::Executor(nodes),
+    }
+}
+
+// Counts nodes in the order subtree_mut indexes them.
+pub fn size(tree: &CharacterBrainDef) -> usize {
+    1 + children(tree).into_iter().map(size).sum::<usize>()
+}
+
+fn subtree(tree: &CharacterBrainDef, index: &mut usize) -> Option<CharacterBrainDef> {
+    if *index == 0 {
+        return Some(tree.clone());
+    }
+    *index -= 1;
+    children(tree)
+        .into_iter()
+        .find_map(|child| subtree(child, index))
+}
+
+fn subtree_mut<'a>(
+    tree: &'a mut CharacterBrainDef,
+    index: &mut usize,
+) -> Option<&'a mut CharacterBrainDef> {
+    if *index == 0 {
+        return Some(tree);
+    }
+    *index -= 1;
+    children_mut(tree)
+        .into_iter()
+        .find_map(|child| subtree_mut(child, index))
+}
+
+fn children_mut(node: &mut CharacterBrainDef) -> Vec<&mut CharacterBrainDef> {
+    match node {
+        UnpoweredTreeDef::Sequence(nodes)
+        | UnpoweredTreeDef::Selector(nodes)
+        | UnpoweredTreeDef::Executor(nodes)
+        | UnpoweredTreeDef::RandomSelector(nodes)
+        | UnpoweredTreeDef::Wrapper(_, nodes) => nodes.iter_mut().collect(),
+        UnpoweredTreeDef::UtilitySelector(scored) => {
+            scored.iter_mut().map(|(_, node)| node).collect()
+        }
+        UnpoweredTreeDef::WeightedSelector(weighted) => {
+            weighted.iter_mut().map(|(_, node)| node).collect()
+        }
+        UnpoweredTreeDef::Repeat(node, _)
+        | UnpoweredTreeDef::RepeatUntilSuccess(node)
+        | UnpoweredTreeDef::RepeatUntilFail(node)
+        | UnpoweredTreeDef::Succeeder(node)
+        | UnpoweredTreeDef::Failer(node)
+        | UnpoweredTreeDef::Inverter(node)
+        | UnpoweredTreeDef::Cooldown(_, node)
+        | UnpoweredTreeDef::Once(node)
+        | UnpoweredTreeDef::EveryNTurns(_, node)
+        | UnpoweredTreeDef::WindUp(_, node)
+        | UnpoweredTreeDef::Channel(_, node) => vec![node.as_mut()],
+        UnpoweredTreeDef::User(_) | UnpoweredTreeDef::Include(_) => vec![],
+    }
+}
+
+// Swaps a random node of the tree for a random node of the donor.
+pub fn crossover(tree: &mut CharacterBrainDef, donor: &CharacterBrainDef, rng: &mut StdRng) {
+    let graft = subtree(donor, &mut rng.gen_range(0..size(donor))).unwrap();
+    let index = rng.gen_range(0..size(tree));
+    *subtree_mut(tree, &mut { index }).unwrap() = graft;
+}
+
+pub fn mutate(tree: &mut CharacterBrainDef, rng: &mut StdRng) {
+    let index = rng.gen_range(0..size(tree));
+    let node = subtree_mut(tree, &mut { index }).unwrap();
+    match node {
+        UnpoweredTreeDef::User(behavior) => *behavior = random_behavior(rng),
+        UnpoweredTreeDef::Sequence(nodes)
+        | UnpoweredTreeDef::Selector(nodes)
+        | UnpoweredTreeDef::Executor(nodes)
+            if nodes.len() > 1 && rng.gen_bool(0.5) =>
+        {
+            let removed = rng.gen_range(0..nodes.len());
+            nodes.remove(removed);
+        }
+        UnpoweredTreeDef::Sequence(nodes)
+        | UnpoweredTreeDef::Selector(nodes)
+        | UnpoweredTreeDef::Executor(nodes) => {
+            let at = rng.gen_range(0..=nodes.len());
+            nodes.insert(at, random_tree(rng, 1));
+        }
+        _ => *node = random_tree(rng, 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutation_and_crossover_keep_trees_serializable() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..50 {
+            let mut tree = random_tree(&mut rng, 3);
+            let donor = random_tree(&mut rng, 3);
+            crossover(&mut tree, &donor, &mut rng);
+            mutate(&mut tree, &mut rng);
+            let source = ron::to_string(&tree).unwrap();
+            let parsed: CharacterBrainDef = ron::from_str(&source).unwrap();
+            assert_eq!(ron::to_string(&parsed).unwrap(), source);
+        }
+    }
+
+    #[test]
+    fn subtrees_are_indexed_in_order() {
+        let mut tree: CharacterBrainDef = ron::from_str(
+            "Sequence([User(FindNearestEnemy), Selector([User(AttackTarget), User(Flank)])])",
+        )
+        .unwrap();
+        assert_eq!(size(&tree), 5);
+        assert_eq!(
+            ron::to_string(&subtree(&tree, &mut 3).unwrap()).unwrap(),
+            "User(AttackTarget)"
+        );
+        *subtree_mut(&mut tree, &mut 4).unwrap() =
+            UnpoweredTreeDef::User(SummonBehaviors::HoldPosition);
+        assert_eq!(
+            ron::to_string(&tree).unwrap(),
+            "Sequence([User(FindNearestEnemy),Selector([User(AttackTarget),User(HoldPosition)])])"
+        );
+    }
+
+    #[test]
+    fn battle_tiles_are_never_shared() {
+        // Every mirrored tile lands on another wave unit, and one unit is off the board.
+        let spawns: Vec<_> = [(0, 0), (7, 7), (1, 6), (6, 1), (2, 5), (5, 2), (9, 9)]
+            .into_iter()
+            .map(|at| (at, "Skeleton".to_string()))
+            .collect();
+        for seed in 0..20 {
+            let tiles = battle_tiles(spawns.clone(), &mut StdRng::seed_from_u64(seed));
+            let mut used: Vec<_> = tiles
+                .iter()
+                .flat_map(|(_, enemy, player)| [*enemy, *player])
+                .collect();
+            assert!(used.iter().all(|(x, y)| *x < 8 && *y < 8), "{:?}", used);
+            used.sort();
+            used.dedup();
+            assert_eq!(used.len(), spawns.len() * 2, "{:?}", tiles);
+            // The wave stands where its file put it.
+            for (at, _) in spawns.iter().filter(|((x, y), _)| *x < 8 && *y < 8) {
+                assert!(tiles.iter().any(|(_, enemy, _)| enemy == at));
+            }
+        }
+    }
+
+    #[test]
+    fn evolving_never_loses_the_best_tree() {
+        let assets = HeadlessAssets::load(Path::new("assets")).unwrap();
+        let options = EvolveOptions {
+            generations: 2,
+            population: 6,
+            trials: 1,
+            keep: 6,
+            ..Default::default()
+        };
+        let mut best = vec![];
+        let result = evolve(&assets, &options, |_, fitness| best.push(fitness));
+        assert!(best.windows(2).all(|pair| pair[1] >= pair[0]), "{:?}", best);
+        assert!(result.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+        assert_eq!(result[0].0, *best.last().unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{prelude::*, summoner::hovered_unit};
+
+// Brains only record traces while the inspector is open.
+#[derive(Resource, Default)]
+pub struct BrainInspector {
+    pub enabled: bool,
+}
+
+#[derive(Component)]
+pub struct BrainInspectorOverlay;
+
+#[derive(Component)]
+pub struct BrainInspectorText;
+
+pub fn toggle_brain_inspector(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut inspector: ResMut<BrainInspector>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        inspector.enabled = !inspector.enabled;
+    }
+}
+
+pub fn spawn_brain_inspector(mut commands: Commands, styles: Res<StyleAssets>) {
+    commands
+        .spawn((
+            NodeBundle {
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            Class::new("inspector"),
+            StyleSheet::new(styles.help.clone()),
+            BrainInspectorOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_sections(vec![]),
+                    ..Default::default()
+                },
+                Class::new("inspector__text"),
+                BrainInspectorText,
+            ));
+        });
+}
+
+pub fn despawn_brain_inspector(
+    mut commands: Commands,
+    query: Query<Entity, With<BrainInspectorOverlay>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn show_hovered_trace(
+    inspector: Res<BrainInspector>,
+    board_mouse_state: Res<BoardMouseState>,
+    mut overlay: Query<&mut Visibility, With<BrainInspectorOverlay>>,
+    mut text_query: Query<&mut Text, With<BrainInspectorText>>,
+    brains: Query<(&Summon, &CharacterBrain)>,
+) {
+    let brain = hovered_unit(&board_mouse_state, brains.iter());
+    let sections = match brain {
+        Some(brain) if inspector.enabled => trace_descriptor(brain.last_trace.as_ref()),
+        _ => {
+            for mut visibility in overlay.iter_mut() {
+                *visibility = Visibility::Hidden;
+            }
+            return;
+        }
+    };
+    for mut text in text_query.iter_mut() {
+        text.sections = sections.clone();
+    }
+    for mut visibility in overlay.iter_mut() {
+        *visibility = Visibility::Visible;
+    }
+}
+
+fn trace_descriptor(trace: Option<&UnpoweredTrace>) -> Vec<TextSection> {
+    let Some(trace) = trace else {
+        return vec![trace_section(
+            "No trace yet, wait for its next turn.".to_string(),
+            Color::GRAY,
+        )];
+    };
+    trace
+        .entries
+        .iter()
+        .map(|entry| {
+            let (state, color) = match entry.state {
+                Some(UnpoweredFunctionState::Complete) => ("Complete", Color::GREEN),
+                Some(UnpoweredFunctionState::Failed) => ("Failed", Color::RED),
+                Some(UnpoweredFunctionState::Waiting) => ("Waiting", Color::YELLOW),
+                None => ("Running", Color::GRAY),
+            };
+            trace_section(
+                format!("{}{} - {}\n", "  ".repeat(entry.depth), entry.label, state),
+                color,
+            )
+        })
+        .collect()
+}
+
+fn trace_section(value: String, color: Color) -> TextSection {
+    TextSection {
+        value,
+        style: TextStyle {
+            font: Default::default(),
+            font_size: 12.0,
+            color,
+        },
+    }
+}
@@ -1,14 +1,20 @@
 use crate::{
     prelude::*,
-    summoner::{spawn_summon_button, SummonButton},
+    summoner::{spawn_summon_button, Fusion, SummonButton},
 };
 
+use super::BrainSummaries;
+
 #[derive(Component)]
 pub struct LootScreen;
 
 #[derive(Component)]
 pub struct LootButton(pub SummonType);
 
+// Marks a loot button that fuses two known summons into its summon, instead of adding it.
+#[derive(Component)]
+pub struct FusionButton(pub Fusion);
+
 #[derive(Component)]
 pub struct LootDescriptor(pub Option<SummonType>);
 
@@ -21,6 +27,8 @@ pub fn setup_loot_screen(
     summons_assets: Res<SummonsAssets>,
     known_summons: Res<KnownSummons>,
     assets_summon_types: Res<Assets<SummonType>>,
+    assets_fusions: Res<Assets<Fusion>>,
+    localization: Res<Localization>,
     mut mana: ResMut<Mana>,
     story_beat: Res<StoryBeat>,
 ) {
@@ -50,7 +58,27 @@ pub fn setup_loot_screen(
             pickable_summons.push(available_summons.remove(idx));
         }
     }
+    // Each available recipe with its output. validate_fusions_on_load already warned about
+    // recipes whose output is missing, but they still mustn't reach the screen.
+    let mut fusions = summons_assets
+        .fusions
+        .values()
+        .map(|fusion| assets_fusions.get(fusion).unwrap())
+        .filter(|fusion| fusion.available(&known_summons))
+        .filter_map(|fusion| {
+            let output = summons_assets
+                .fused_summons
+                .get(fusion.output.as_str())
+                .and_then(|summon| assets_summon_types.get(summon));
+            if output.is_none() {
+                warn!("Leaving out fusion into missing summon {}", fusion.output);
+            }
+            Some((fusion, output?))
+        })
+        .collect::<Vec<_>>();
+    fusions.sort_by(|(a, _), (b, _)| a.output.cmp(&b.output));
     let mut buttons = vec![];
+    let mut fusion_buttons = vec![];
     commands
         .spawn((
             NodeBundle::default(),
@@ -63,7 +91,7 @@ pub fn setup_loot_screen(
                 TextBundle {
                     text: Text::from_sections(vec![
                         TextSection {
-                            value: "Select a new summon!\n".to_string(),
+                            value: format!("{}\n", localization.text("loot.select")),
                             style: TextStyle {
                                 font: Default::default(),
                                 font_size: 20.0,
@@ -72,12 +100,12 @@ pub fn setup_loot_screen(
                         },
                         TextSection {
                             value: if story_beat.mana_gained > 0 {
-                                format!(
-                                    "You gained {} mana!\nYour new max is: {}",
-                                    story_beat.mana_gained, mana.max_mana
+                                localization.format(
+                                    "loot.mana_gained",
+                                    &[("mana", &story_beat.mana_gained), ("max", &mana.max_mana)],
                                 )
                             } else {
-                                format!("Your max mana is: {}", mana.max_mana)
+                                localization.format("loot.max_mana", &[("max", &mana.max_mana)])
                             },
                             style: TextStyle {
                                 font: Default::default(),
@@ -95,10 +123,50 @@ pub fn setup_loot_screen(
                 .with_children(|parent| {
                     for summon in pickable_summons {
                         let summon = assets_summon_types.get(summon).unwrap();
-                        let button = spawn_summon_button(parent, &styles, &texture_assets, summon);
+                        let button = spawn_summon_button(
+                            parent,
+                            &styles,
+                            &texture_assets,
+                            &localization,
+                            summon,
+                        );
                         buttons.push((button, LootButton(summon.clone())));
                     }
                 });
+            if !fusions.is_empty() {
+                parent.spawn((
+                    TextBundle {
+                        text: Text::from_sections(vec![TextSection {
+                            value: localization.text("loot.fuse"),
+                            style: TextStyle {
+                                font: Default::default(),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        }]),
+                        ..Default::default()
+                    },
+                    Class::new("loot__text"),
+                ));
+                parent
+                    .spawn((NodeBundle::default(), Class::new("loot__summons")))
+                    .with_children(|parent| {
+                        for (fusion, summon) in fusions {
+                            let button = spawn_summon_button(
+                                parent,
+                                &styles,
+                                &texture_assets,
+                                &localization,
+                                summon,
+                            );
+                            fusion_buttons.push((
+                                button,
+                                LootButton(summon.clone()),
+                                FusionButton(fusion.clone()),
+                            ));
+                        }
+                    });
+            }
             parent.spawn((
                 TextBundle {
                     text: Text::from_sections(vec![TextSection {
@@ -111,30 +179,50 @@ pub fn setup_loot_screen(
                     }]),
                     ..Default::default()
                 },
-                Class::new("loot__text"),
+                Class::new("loot__descriptor"),
                 LootDescriptor(None),
             ));
         });
     for (button, component) in buttons {
         commands.entity(button).insert(component);
     }
+    for (button, loot, fusion) in fusion_buttons {
+        commands.entity(button).insert((loot, fusion));
+    }
 }
 
 pub fn handle_loot_button_click(
     mut state: ResMut<NextState<GameState>>,
     mut known_summons: ResMut<KnownSummons>,
+    mut veterans: ResMut<Veterans>,
+    summaries: Res<BrainSummaries>,
+    localization: Res<Localization>,
     mut query: Query<
-        (&mut Class, &mut SummonButton, &LootButton, &Interaction),
+        (
+            &mut Class,
+            &mut SummonButton,
+            &LootButton,
+            Option<&FusionButton>,
+            &Interaction,
+        ),
         Changed<Interaction>,
     >,
     mut descriptor_query: Query<(&mut Text, &mut LootDescriptor)>,
 ) {
-    for (mut class, mut summon, loot, interaction) in query.iter_mut() {
+    for (mut class, mut summon, loot, fusion, interaction) in query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
                 if summon.0 {
                     state.set(GameState::Summoning);
-                    known_summons.add(loot.0.clone());
+                    if let Some(FusionButton(fusion)) = fusion {
+                        known_summons.fuse((&fusion.inputs.0, &fusion.inputs.1), loot.0.clone());
+                        // The fused summon keeps the experience both halves earned.
+                        let experience = veterans.experience(&fusion.inputs.0)
+                            + veterans.experience(&fusion.inputs.1);
+                        veterans.gain(&fusion.output, experience);
+                    } else {
+                        known_summons.add(loot.0.clone());
+                    }
                 } else {
                     summon.0 = true;
                     class.add("selected");
@@ -144,7 +232,24 @@ pub fn handle_loot_button_click(
                 class.add("hovered");
                 for (mut text, mut descriptor) in descriptor_query.iter_mut() {
                     descriptor.0 = Some(loot.0.clone());
-                    text.sections = loot.0.descriptor();
+                    text.sections = loot.0.descriptor(
+                        &loot.0.veteran_stats(veterans.experience(loot.0.name())),
+                        &summaries.describe(&loot.0, &localization),
+                        &localization,
+                    );
+                    if let Some(FusionButton(fusion)) = fusion {
+                        text.sections.insert(
+                            1,
+                            TextSection {
+                                value: format!("\n{}", fusion.tagline(&localization)),
+                                style: TextStyle {
+                                    font: Default::default(),
+                                    font_size: 16.0,
+                                    color: Color::ORANGE,
+                                },
+                            },
+                        );
+                    }
                 }
             }
             Interaction::None => {
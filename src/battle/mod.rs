@@ -3,22 +3,45 @@ use crate::{
     summons::{spawn_summon, Summon},
 };
 
+pub mod blackboard;
 pub mod bt;
+pub mod evolve;
+pub mod inspector;
 pub mod loot;
 pub mod runner;
+pub mod simulation;
 pub mod stats;
+pub mod summary;
+pub mod validation;
 pub struct BattlePlugin;
+pub use blackboard::*;
 pub use bt::*;
+pub use evolve::*;
+pub use inspector::*;
 pub use loot::*;
 pub use runner::*;
+pub use simulation::*;
 pub use stats::*;
+pub use summary::*;
+pub use validation::*;
 
 impl Plugin for BattlePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TurnOrder>()
+            .init_resource::<BattleRng>()
+            .init_resource::<BrainInspector>()
             .init_resource::<BattleSpeed>()
             .init_resource::<BattleTimer>()
+            .init_resource::<BrainSummaries>()
             .add_event::<AttackEvent>()
+            .add_systems(OnExit(GameState::Loading), validate_brains_on_load)
+            .add_systems(
+                Update,
+                summarize_brains.run_if(
+                    resource_exists::<BrainAssets>.and_then(resource_changed::<Localization>),
+                ),
+            )
+            .add_systems(Update, reload_changed_brains)
             .add_systems(
                 Update,
                 (
@@ -26,10 +49,15 @@ impl Plugin for BattlePlugin {
                     animate_battle,
                     animate_battle_text,
                     show_auras_overhead,
+                    show_wind_up,
+                    toggle_brain_inspector,
+                    show_hovered_trace,
                 )
                     .run_if(in_state(GameState::Battling)),
             )
             .add_systems(Update, (run_battle).run_if(in_state(GameState::Battling)))
+            .add_systems(OnEnter(GameState::Battling), spawn_brain_inspector)
+            .add_systems(OnExit(GameState::Battling), despawn_brain_inspector)
             .add_systems(PostUpdate, end_battle.run_if(in_state(GameState::Battling)))
             .add_systems(OnEnter(GameState::Looting), setup_loot_screen)
             .add_systems(
@@ -1,14 +1,26 @@
-use bevy::utils::hashbrown::HashSet;
+use crate::{
+    prelude::*,
+    summoner::{KILL_EXPERIENCE, SURVIVAL_EXPERIENCE},
+    summons::OverheadText,
+};
 
-use crate::{prelude::*, summons::OverheadText};
-
-use super::DeathCharacterBrain;
+use super::{BrainInspector, DeathCharacterBrain};
 
 #[derive(Resource, Default)]
 pub struct TurnOrder {
     pub order: Vec<Entity>,
 }
 
+// Seeds every brain's choices, so a battle can be replayed from one seed.
+#[derive(Resource)]
+pub struct BattleRng(pub StdRng);
+
+impl Default for BattleRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
 #[derive(Resource)]
 pub struct BattleSpeed(pub f32);
 
@@ -38,6 +50,7 @@ pub fn end_battle(
     mut next_state: ResMut<NextState<GameState>>,
     fighters: Query<(Entity, &Faction, &Summon, &CharacterStats)>,
     damage_text: Query<Entity, With<DamageText>>,
+    mut veterans: ResMut<Veterans>,
     mut story: ResMut<Story>,
     mut story_beat: ResMut<StoryBeat>,
     sounds: Res<AudioAssets>,
@@ -58,6 +71,11 @@ pub fn end_battle(
         for entity in damage_text.iter() {
             commands.entity(entity).despawn_recursive();
         }
+        for (_entity, faction, summon, stats) in fighters.iter() {
+            if *faction == Faction::Player && stats.health > 0 {
+                veterans.gain(summon.summon_type.name(), SURVIVAL_EXPERIENCE);
+            }
+        }
         let beats = if enemy_units == 0 {
             commands.spawn(AudioBundle {
                 source: sounds.victory_sting.clone(),
@@ -93,6 +111,9 @@ pub fn run_battle(
     time: Res<Time>,
     mut commands: Commands,
     mut turn_order: ResMut<TurnOrder>,
+    mut battle_rng: ResMut<BattleRng>,
+    inspector: Res<BrainInspector>,
+    mut veterans: ResMut<Veterans>,
     mut fighters: Query<(
         Entity,
         &Faction,
@@ -114,65 +135,37 @@ pub fn run_battle(
         }
         turn_order
             .order
-            .sort_by_cached_key(|_| rand::random::<u32>());
+            .sort_by_cached_key(|_| battle_rng.0.gen::<u32>());
     }
     ticker.0 = 0.;
-    let mut player_units = vec![];
-    let mut enemy_units = vec![];
-    let mut dead_units = HashSet::new();
-    for (_entity, faction, summon, stats, _brain, _death) in fighters.iter() {
-        match faction {
-            Faction::Player => player_units.push((summon.x, summon.y)),
-            Faction::Enemy => enemy_units.push((summon.x, summon.y)),
-        }
-        if stats.health <= 0 {
-            dead_units.insert((summon.x, summon.y));
-        }
-    }
-    if player_units.is_empty() && enemy_units.is_empty() {
+    let everyone: Vec<_> = fighters
+        .iter()
+        .map(|(_entity, faction, summon, stats, _brain, _death)| {
+            (faction.clone(), (summon.x, summon.y), stats.clone())
+        })
+        .collect();
+    if everyone.is_empty() {
         return;
     }
     let next_turn = turn_order.order.pop().unwrap();
     let mut attacks = vec![];
     let mut auras = vec![];
+    let mut kills = vec![];
     if let Ok((entity, faction, mut summon, mut stats, mut brain, mut death_brain)) =
         fighters.get_mut(next_turn)
     {
         if stats.health <= 0 {
             commands.entity(entity).despawn_recursive();
         }
-        let model = BehaviorModel {
-            position: (summon.x, summon.y),
-            stats: stats.clone(),
-            enemies: match faction {
-                Faction::Player => enemy_units.clone(),
-                Faction::Enemy => player_units.clone(),
-            }
-            .iter()
-            .filter(|(x, y)| !dead_units.contains(&(*x, *y)))
-            .cloned()
-            .collect(),
-            allies: match faction {
-                Faction::Player => player_units,
-                Faction::Enemy => enemy_units,
-            }
-            .iter()
-            .filter(|(x, y)| !dead_units.contains(&(*x, *y)))
-            .cloned()
-            .collect(),
-        };
-        let mut controller = BehaviorController {
-            actions: vec![],
-            picked_location: None,
-            picked_index: None,
-            picked_aura: None,
-        };
-        if stats.health <= 0 {
+        let model = BehaviorModel::for_fighter((summon.x, summon.y), &stats, faction, &everyone);
+        let controller: BehaviorController = if stats.health <= 0 {
             info!("Death brain!");
-            death_brain.0.tree.resume_with(&model, &mut controller);
+            death_brain
+                .0
+                .think(&model, battle_rng.0.gen(), inspector.enabled)
         } else {
-            brain.tree.resume_with(&model, &mut controller);
-        }
+            brain.think(&model, battle_rng.0.gen(), inspector.enabled)
+        };
         for action in controller.actions {
             match action {
                 Action::Move { movement, target } => {
@@ -212,7 +205,18 @@ pub fn run_battle(
                 target,
                 damage: attack.damage,
             });
+            let killed = stats.health > 0 && stats.health <= attack.damage;
             stats.health -= attack.damage;
+            if killed {
+                kills.push(entity);
+            }
+        }
+    }
+    for attacker in kills {
+        if let Ok((_entity, Faction::Player, summon, _stats, _brain, _death)) =
+            fighters.get(attacker)
+        {
+            veterans.gain(summon.summon_type.name(), KILL_EXPERIENCE);
         }
     }
     for (effect, target) in auras {
@@ -258,14 +262,12 @@ pub fn animate_battle(
         transform.translation = translation;
         if stats.health <= 0 {
             if !stats.is_dead {
-                commands.spawn(AudioBundle {
-                    source: sounds
-                        .death_stings
-                        .get(summon.summon_type.tribe.death_sting())
-                        .unwrap()
-                        .clone(),
-                    ..Default::default()
-                });
+                if let Some(source) = sounds.summon_sound(&summon.summon_type, SoundCue::Death) {
+                    commands.spawn(AudioBundle {
+                        source,
+                        ..Default::default()
+                    });
+                }
                 stats.kill();
             }
             transform.scale = transform.scale.lerp(Vec3::splat(0.1), t);
@@ -274,7 +276,7 @@ pub fn animate_battle(
         }
     }
     for attack in attacks.read() {
-        if let Ok((_, mut transform, _)) = summon_query.get_mut(attack.target) {
+        if let Ok((summon, mut transform, _)) = summon_query.get_mut(attack.target) {
             transform.scale = Vec3::splat(0.9);
             commands.spawn((
                 Text2dBundle {
@@ -293,19 +295,56 @@ pub fn animate_battle(
                 },
                 DamageText(0.0),
             ));
-            commands.spawn(AudioBundle {
-                source: sounds.hurt.clone(),
-                ..Default::default()
-            });
+            if let Some(source) = sounds.summon_sound(&summon.summon_type, SoundCue::Hurt) {
+                commands.spawn(AudioBundle {
+                    source,
+                    ..Default::default()
+                });
+            }
         }
-        if let Ok((_, mut transform, _)) = summon_query.get_mut(attack.attacker) {
+        if let Ok((summon, mut transform, _)) = summon_query.get_mut(attack.attacker) {
             transform.translation.y += 8.;
+            if let Some(source) = sounds.summon_sound(&summon.summon_type, SoundCue::Attack) {
+                commands.spawn(AudioBundle {
+                    source,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+// The tint a unit had before it started to glow, put back once the wind-up goes off.
+#[derive(Component)]
+pub struct WindUpGlow(pub Color);
+
+// Units waiting on a multi-turn action pulse a warm glow until it goes off.
+pub fn show_wind_up(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut units: Query<(Entity, &CharacterBrain, &mut Sprite, Option<&WindUpGlow>)>,
+) {
+    let pulse = (time.elapsed_seconds() * 6.).sin() * 0.5 + 0.5;
+    for (entity, brain, mut sprite, glow) in units.iter_mut() {
+        match (brain.is_winding_up(), glow) {
+            (true, glow) => {
+                if glow.is_none() {
+                    commands.entity(entity).insert(WindUpGlow(sprite.color));
+                }
+                sprite.color = Color::rgb(1., 0.6 + 0.4 * pulse, 0.2 + 0.8 * pulse);
+            }
+            (false, Some(glow)) => {
+                sprite.color = glow.0;
+                commands.entity(entity).remove::<WindUpGlow>();
+            }
+            (false, None) => {}
         }
     }
 }
 
 pub fn show_auras_overhead(
     stats: Query<(Entity, &CharacterStats, &Summon)>,
+    localization: Res<Localization>,
     mut overhead_query: Query<(&Parent, &mut Text), With<OverheadText>>,
 ) {
     for (parent, mut text) in overhead_query.iter_mut() {
@@ -314,7 +353,7 @@ pub fn show_auras_overhead(
                 .applied_auras
                 .iter()
                 .fold("".to_string(), |acc, aura| {
-                    format!("{}\n{}", acc, aura.name())
+                    format!("{}\n{}", acc, aura.display_name(&localization))
                 });
         }
     }
@@ -0,0 +1,168 @@
+use crate::prelude::*;
+
+// Rounds before a headless battle is called a draw.
+pub const MAX_SIMULATED_ROUNDS: usize = 100;
+
+pub struct SimulatedUnit {
+    pub faction: Faction,
+    pub position: (usize, usize),
+    pub stats: CharacterStats,
+    pub brain: CharacterBrain,
+    pub death_brain: CharacterBrain,
+    // Set once a dead unit has taken its last turn, like a despawned entity.
+    pub removed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedOutcome {
+    // None for a draw.
+    pub winner: Option<Faction>,
+    pub rounds: usize,
+    pub player_health: i32,
+    pub enemy_health: i32,
+}
+
+// Plays a battle out the way run_battle and end_battle do, without the ECS or any timers.
+pub fn simulate_battle(mut units: Vec<SimulatedUnit>, rng: &mut StdRng) -> SimulatedOutcome {
+    let mut rounds = 0;
+    let mut winner = None;
+    'battle: while rounds < MAX_SIMULATED_ROUNDS {
+        rounds += 1;
+        let mut order = vec![];
+        for (index, unit) in units.iter_mut().enumerate() {
+            if !unit.removed {
+                order.push(index);
+                unit.stats.stamina += unit.stats.stamina_regen;
+            }
+        }
+        order.sort_by_cached_key(|_| rng.gen::<u32>());
+        for index in order {
+            take_turn(&mut units, index, rng.gen());
+            let standing = |faction: Faction| {
+                units
+                    .iter()
+                    .any(|unit| !unit.removed && unit.faction == faction)
+            };
+            match (standing(Faction::Player), standing(Faction::Enemy)) {
+                (true, true) => {}
+                (true, false) => winner = Some(Faction::Player),
+                (false, _) => winner = Some(Faction::Enemy),
+            }
+            if winner.is_some() {
+                break 'battle;
+            }
+        }
+    }
+    let health = |faction: Faction| {
+        units
+            .iter()
+            .filter(|unit| unit.faction == faction)
+            .map(|unit| unit.stats.health.max(0))
+            .sum()
+    };
+    SimulatedOutcome {
+        winner,
+        rounds,
+        player_health: health(Faction::Player),
+        enemy_health: health(Faction::Enemy),
+    }
+}
+
+fn take_turn(units: &mut [SimulatedUnit], index: usize, seed: u64) {
+    if units[index].removed {
+        return;
+    }
+    let everyone: Vec<_> = units
+        .iter()
+        .filter(|unit| !unit.removed)
+        .map(|unit| (unit.faction.clone(), unit.position, unit.stats.clone()))
+        .collect();
+    let unit = &mut units[index];
+    let model = BehaviorModel::for_fighter(unit.position, &unit.stats, &unit.faction, &everyone);
+    let controller = if unit.stats.health <= 0 {
+        unit.removed = true;
+        unit.death_brain.think(&model, seed, false)
+    } else {
+        unit.brain.think(&model, seed, false)
+    };
+    let mut attacks = vec![];
+    let mut auras = vec![];
+    for action in controller.actions {
+        match action {
+            Action::Move { movement, target } => {
+                for _ in 0..movement.tiles {
+                    let (x, y) = unit.position;
+                    let next_location = movement.next_location(x, y, target.0, target.1);
+                    if !model.location_occupied(next_location.0, next_location.1) {
+                        unit.position = next_location;
+                        unit.stats.stamina -= movement.stamina_cost;
+                    }
+                }
+            }
+            Action::Attack { attack, target } => attacks.push((attack, target)),
+            Action::Aura { effect, target } => auras.push((effect, target)),
+        }
+    }
+    // The dying unit is still on the board until its death brain has acted.
+    let at = |units: &[SimulatedUnit], target: (usize, usize)| {
+        units
+            .iter()
+            .enumerate()
+            .position(|(other, unit)| unit.position == target && (!unit.removed || other == index))
+    };
+    for (attack, target) in attacks {
+        let attacker = &mut units[index].stats;
+        if attacker.stamina >= attack.stamina_cost {
+            attacker.stamina -= attack.stamina_cost;
+        }
+        if let Some(target) = at(units, target) {
+            units[target].stats.health -= attack.damage;
+        }
+    }
+    for (effect, target) in auras {
+        if let Some(target) = at(units, target) {
+            units[target].stats.apply_aura(effect);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::HeadlessAssets;
+
+    fn assets() -> HeadlessAssets {
+        HeadlessAssets::load(std::path::Path::new("assets")).unwrap()
+    }
+
+    #[test]
+    fn shipped_waves_load_with_known_units() {
+        let assets = assets();
+        assert!(!assets.waves.is_empty());
+        for (name, wave) in &assets.waves {
+            for (_, summon) in wave.iter() {
+                assert!(
+                    assets.unit(summon, Faction::Enemy, (0, 0), None).is_ok(),
+                    "{}: {}",
+                    name,
+                    summon
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fighters_beat_an_idle_army() {
+        let assets = assets();
+        let idle: CharacterBrainDef = ron::from_str("Sequence([])").unwrap();
+        let units = vec![
+            assets.unit("Ghoul", Faction::Player, (3, 0), None).unwrap(),
+            assets
+                .unit("Ghoul", Faction::Enemy, (3, 7), Some(&idle))
+                .unwrap(),
+        ];
+        let outcome = simulate_battle(units, &mut StdRng::seed_from_u64(0));
+        assert_eq!(outcome.winner, Some(Faction::Player));
+        assert_eq!(outcome.enemy_health, 0);
+    }
+}
@@ -78,6 +78,16 @@ impl AuraEffect {
         }
     }
 
+    pub fn applies_to(&self, tribe: &Tribe) -> bool {
+        let tribes = match self {
+            AuraEffect::Speed(_, _, tribes) => tribes,
+            AuraEffect::Attack(_, _, tribes) => tribes,
+            AuraEffect::Health(_, _, tribes) => tribes,
+            AuraEffect::Range(_, _, tribes) => tribes,
+        };
+        tribes.is_empty() || tribes.contains(tribe)
+    }
+
     pub fn is_friendly(&self) -> bool {
         match self {
             AuraEffect::Speed(_, amount, _) => amount > &0,
@@ -87,22 +97,58 @@ impl AuraEffect {
         }
     }
 
-    pub fn tagline(&self) -> String {
+    // The name players see, which a locale may translate as "aura.<name>".
+    pub fn display_name(&self, localization: &Localization) -> String {
+        localization.content(&format!("aura.{}", self.name()), self.name())
+    }
+
+    fn stat_key(&self) -> &str {
         match self {
-            AuraEffect::Speed(name, amount, _) => {
-                format!("{}: {}Speed", name, if amount > &0 { "+" } else { "-" })
-            }
-            AuraEffect::Attack(name, amount, _) => {
-                format!("{}: {}Damage", name, if amount > &0 { "+" } else { "-" })
-            }
-            AuraEffect::Health(name, amount, _) => {
-                format!("{}: {}Health", name, if amount > &0 { "+" } else { "-" })
-            }
-            AuraEffect::Range(name, amount, _) => {
-                format!("{}: {}Range", name, if amount > &0 { "+" } else { "-" })
-            }
+            AuraEffect::Speed(..) => "stat.speed",
+            AuraEffect::Attack(..) => "stat.damage",
+            AuraEffect::Health(..) => "stat.health",
+            AuraEffect::Range(..) => "stat.range",
         }
     }
+
+    // Like the tagline, with the amount and the tribes it affects, e.g.
+    // "Blessing: +1 Health for Angel, Undead".
+    pub fn description(&self, localization: &Localization) -> String {
+        let (amount, tribes) = match self {
+            AuraEffect::Speed(_, amount, tribes)
+            | AuraEffect::Attack(_, amount, tribes)
+            | AuraEffect::Health(_, amount, tribes)
+            | AuraEffect::Range(_, amount, tribes) => (amount, tribes),
+        };
+        let tribes = match tribes.is_empty() {
+            true => localization.text("aura.every_tribe"),
+            false => tribes
+                .iter()
+                .map(|tribe| tribe.name(localization))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        localization.format(
+            "aura.description",
+            &[
+                ("name", &self.display_name(localization)),
+                ("amount", &format!("{:+}", amount)),
+                ("stat", &localization.text(self.stat_key())),
+                ("tribes", &tribes),
+            ],
+        )
+    }
+
+    pub fn tagline(&self, localization: &Localization) -> String {
+        localization.format(
+            "aura.tagline",
+            &[
+                ("name", &self.display_name(localization)),
+                ("sign", &if self.is_friendly() { "+" } else { "-" }),
+                ("stat", &localization.text(self.stat_key())),
+            ],
+        )
+    }
 }
 
 #[derive(Component, Debug, Clone, PartialEq, Eq)]
@@ -118,9 +164,15 @@ pub struct CharacterStats {
     pub movements: Vec<Movement>,
     pub auras: Vec<AuraEffect>,
     pub applied_auras: Vec<AuraEffect>,
+    // What the unit's summon type had earned when it was summoned.
+    pub experience: i32,
 }
 
 impl CharacterStats {
+    pub fn max_damage(&self) -> i32 {
+        self.attacks.iter().map(|a| a.damage).max().unwrap_or(0)
+    }
+
     pub fn kill(&mut self) {
         self.is_dead = true;
     }
@@ -164,64 +216,56 @@ impl CharacterStats {
         }
     }
 
-    pub fn descriptor(&self) -> Vec<TextSection> {
-        vec![
-            TextSection {
-                value: format!("{} - {:?}\n", self.name, self.tribe),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 16.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: format!("Health: {}/{} - ", self.health, self.max_health),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: format!("Stamina: {}\n", self.stamina),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: format!(
-                    "Max Range: {} - Max Damage {}\n",
-                    self.attacks.iter().map(|a| a.range).max().unwrap_or(0),
-                    self.attacks.iter().map(|a| a.damage).max().unwrap_or(0)
-                ),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: if self.auras.is_empty() {
-                    "".to_string()
+    // The stat card's lines, with the unit's current health and any bonuses applied.
+    pub fn stat_lines(&self, localization: &Localization) -> Vec<String> {
+        let mut lines = vec![
+            localization.format(
+                "card.health",
+                &[("health", &self.health), ("max", &self.max_health)],
+            ),
+            localization.format(
+                "card.stamina",
+                &[("stamina", &self.stamina), ("regen", &self.stamina_regen)],
+            ),
+        ];
+        for attack in &self.attacks {
+            lines.push(localization.format(
+                "card.attack",
+                &[
+                    ("damage", &attack.damage),
+                    ("range", &attack.range),
+                    ("stamina", &attack.stamina_cost),
+                ],
+            ));
+        }
+        for movement in &self.movements {
+            lines.push(localization.format(
+                if movement.tiles == 1 {
+                    "card.move_one"
                 } else {
-                    format!(
-                        "Effects Given: {}\n",
-                        self.auras
-                            .iter()
-                            .map(|a| a.tagline())
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    )
-                },
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
+                    "card.move"
                 },
-            },
-        ]
+                &[
+                    ("tiles", &movement.tiles),
+                    ("stamina", &movement.stamina_cost),
+                ],
+            ));
+        }
+        for aura in &self.auras {
+            lines.push(
+                localization.format("card.aura", &[("aura", &aura.description(localization))]),
+            );
+        }
+        if !self.applied_auras.is_empty() {
+            let auras = self
+                .applied_auras
+                .iter()
+                .map(|aura| aura.tagline(localization))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(localization.format("card.affected_by", &[("auras", &auras)]));
+        }
+        lines
     }
 }
 
@@ -0,0 +1,267 @@
+use std::borrow::Borrow;
+
+use crate::prelude::*;
+
+use super::{children, resolve_brain, SummonBehaviors, SummonWrapperDef};
+
+// Readable lines for each brain, by brain name, for stat cards. Rebuilt when the language
+// changes.
+#[derive(Resource, Default)]
+pub struct BrainSummaries(HashMap<String, Vec<String>>);
+
+impl BrainSummaries {
+    // What the summon does while alive, then what it does as it dies.
+    pub fn describe(&self, summon: &SummonType, localization: &Localization) -> Vec<String> {
+        let death_brain = match summon.death_brain() {
+            "" => "death",
+            name => name,
+        };
+        let mut lines = self.0.get(summon.brain()).cloned().unwrap_or_default();
+        if let Some(death) = self.0.get(death_brain) {
+            lines.extend(
+                death
+                    .iter()
+                    .map(|line| localization.format("brain.on_death", &[("action", line)])),
+            );
+        }
+        lines
+    }
+}
+
+pub fn summarize_brains(
+    brains: Res<BrainAssets>,
+    brain_defs: Res<Assets<CharacterBrainDef>>,
+    localization: Res<Localization>,
+    mut summaries: ResMut<BrainSummaries>,
+) {
+    for (name, handle) in brains.brains.iter() {
+        let name: &str = name.borrow();
+        // validate_brains_on_load already warns about brains that don't resolve.
+        if let Ok(brain) = resolve_brain(handle.id(), &brains, &brain_defs) {
+            summaries
+                .0
+                .insert(name.to_string(), summarize_brain(&brain, &localization));
+        }
+    }
+}
+
+// One line per thing the brain can do, in the order the tree tries them, without repeats.
+pub fn summarize_brain(brain: &CharacterBrainDef, localization: &Localization) -> Vec<String> {
+    let mut summary = Summary {
+        localization,
+        lines: vec![],
+    };
+    summary.node(brain, &mut Context::default());
+    summary.lines
+}
+
+// What the nodes before this one have set up.
+#[derive(Clone, Default)]
+struct Context {
+    target: Option<String>,
+    // The locale key for spreading the picked aura.
+    aura: Option<&'static str>,
+    // How often the enclosing decorators let this run, outermost first.
+    timings: Vec<String>,
+}
+
+struct Summary<'a> {
+    localization: &'a Localization,
+    lines: Vec<String>,
+}
+
+impl<'a> Summary<'a> {
+    fn push(&mut self, context: &Context, key: &str, args: &[(&str, &dyn std::fmt::Display)]) {
+        let mut line = self.localization.format(key, args);
+        for timing in context.timings.iter().rev() {
+            line = self
+                .localization
+                .format("brain.timed", &[("action", &line), ("timing", timing)]);
+        }
+        if !self.lines.contains(&line) {
+            self.lines.push(line);
+        }
+    }
+
+    fn timed(&mut self, node: &CharacterBrainDef, context: &Context, timing: String) {
+        let mut inner = context.clone();
+        inner.timings.push(timing);
+        self.node(node, &mut inner);
+    }
+
+    fn node(&mut self, node: &CharacterBrainDef, context: &mut Context) {
+        let localization = self.localization;
+        let text = |key: &str| Some(localization.text(key));
+        let target = context
+            .target
+            .clone()
+            .unwrap_or_else(|| localization.text("brain.target"));
+        match node {
+            // Sequences and executors hand their picks on to later children.
+            UnpoweredTreeDef::Sequence(nodes) | UnpoweredTreeDef::Executor(nodes) => {
+                for node in nodes {
+                    self.node(node, context);
+                }
+            }
+            UnpoweredTreeDef::Wrapper(wrapper, nodes) => {
+                let mut inner = context.clone();
+                inner.target = match wrapper {
+                    SummonWrapperDef::ForAllAllies => text("brain.every_ally"),
+                    SummonWrapperDef::ForAllEnemies => text("brain.every_enemy"),
+                    SummonWrapperDef::ForAllAlliesInRange(range) => {
+                        Some(localization.format("brain.allies_in_range", &[("range", range)]))
+                    }
+                    SummonWrapperDef::ForAllEnemiesInRange(range) => {
+                        Some(localization.format("brain.enemies_in_range", &[("range", range)]))
+                    }
+                };
+                for node in nodes {
+                    self.node(node, &mut inner);
+                }
+            }
+            UnpoweredTreeDef::Once(node) => {
+                self.timed(node, context, localization.text("brain.once"))
+            }
+            UnpoweredTreeDef::Cooldown(turns, node) => self.timed(
+                node,
+                context,
+                localization.format("brain.cooldown", &[("turns", turns)]),
+            ),
+            UnpoweredTreeDef::EveryNTurns(turns, node) => self.timed(
+                node,
+                context,
+                localization.format("brain.every", &[("turns", turns)]),
+            ),
+            UnpoweredTreeDef::WindUp(turns, node) => self.timed(
+                node,
+                context,
+                localization.format("brain.wind_up", &[("turns", turns)]),
+            ),
+            UnpoweredTreeDef::Channel(turns, node) => self.timed(
+                node,
+                context,
+                localization.format("brain.channel", &[("turns", turns)]),
+            ),
+            UnpoweredTreeDef::User(behavior) => match behavior {
+                SummonBehaviors::FindNearestEnemy => context.target = text("brain.nearest_enemy"),
+                SummonBehaviors::FindNearestAlly => context.target = text("brain.nearest_ally"),
+                SummonBehaviors::FindRandomEnemy => context.target = text("brain.random_enemy"),
+                SummonBehaviors::FindRandomAlly => context.target = text("brain.random_ally"),
+                SummonBehaviors::FindWeakestEnemy => context.target = text("brain.weakest_enemy"),
+                SummonBehaviors::FindStrongestEnemy => {
+                    context.target = text("brain.strongest_enemy")
+                }
+                SummonBehaviors::FindMostThreateningEnemy => {
+                    context.target = text("brain.most_threatening_enemy")
+                }
+                SummonBehaviors::FindEnemyOfTribe(tribe) => {
+                    context.target = Some(localization.format(
+                        "brain.enemy_of_tribe",
+                        &[("tribe", &tribe.name(localization))],
+                    ))
+                }
+                SummonBehaviors::FindAllyNeedingAura => {
+                    context.target = text("brain.ally_needing_aura")
+                }
+                SummonBehaviors::RecallTarget(_) => context.target = text("brain.remembered"),
+                SummonBehaviors::PickFriendlyAura => context.aura = Some("brain.blesses"),
+                SummonBehaviors::PickUnfriendlyAura => context.aura = Some("brain.curses"),
+                SummonBehaviors::AttackTarget => {
+                    self.push(context, "brain.attacks", &[("target", &target)])
+                }
+                SummonBehaviors::MoveTowardsTarget => {
+                    self.push(context, "brain.moves_towards", &[("target", &target)])
+                }
+                SummonBehaviors::MoveAwayFromTarget => {
+                    self.push(context, "brain.moves_away", &[("target", &target)])
+                }
+                SummonBehaviors::RefreshAuraForTarget => self.push(
+                    context,
+                    context.aura.unwrap_or("brain.spreads_aura"),
+                    &[("target", &target)],
+                ),
+                SummonBehaviors::KeepDistance(tiles) => self.push(
+                    context,
+                    "brain.keeps_distance",
+                    &[("tiles", tiles), ("target", &target)],
+                ),
+                SummonBehaviors::StayAdjacentToAlly(tribe) => self.push(
+                    context,
+                    "brain.stays_adjacent",
+                    &[("tribe", &tribe.name(localization))],
+                ),
+                SummonBehaviors::Flank => {
+                    self.push(context, "brain.flanks", &[("target", &target)])
+                }
+                SummonBehaviors::HoldPosition => self.push(context, "brain.holds", &[]),
+                _ => {}
+            },
+            // Only one branch runs, so picks made in one don't carry over to the next.
+            _ => {
+                for node in children(node) {
+                    self.node(node, &mut context.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHASE: &str =
+        "Sequence([User(FindNearestEnemy), User(PickRandomMovement), User(MoveTowardsTarget)])";
+
+    #[test]
+    fn brains_read_as_sentences() {
+        let localization = Localization::english(&[
+            ("brain.attacks", "Attacks {target}"),
+            ("brain.moves_towards", "Moves towards {target}"),
+            ("brain.moves_away", "Moves away from {target}"),
+            ("brain.blesses", "Blesses {target}"),
+            ("brain.curses", "Curses {target}"),
+            ("brain.spreads_aura", "Spreads its aura to {target}"),
+            ("brain.timed", "{action} {timing}"),
+            ("brain.every", "every {turns} turns"),
+            ("brain.nearest_enemy", "the nearest enemy"),
+            ("brain.weakest_enemy", "the weakest enemy"),
+            ("brain.every_ally", "every ally"),
+            ("brain.every_enemy", "every enemy"),
+            ("brain.allies_in_range", "every ally within {range} tiles"),
+        ]);
+        let summary = |brain: &str| summarize_brain(&ron::from_str(brain).unwrap(), &localization);
+        assert_eq!(
+            summary(&format!(
+                "Selector([Sequence([User(FindNearestEnemy), User(PickRandomAttack), User(AttackTarget), User(PickRandomMovement), User(MoveAwayFromTarget)]), {}])",
+                CHASE
+            )),
+            vec![
+                "Attacks the nearest enemy",
+                "Moves away from the nearest enemy",
+                "Moves towards the nearest enemy",
+            ]
+        );
+        assert_eq!(
+            summary(&format!(
+                "Executor([User(PickFriendlyAura), Wrapper(ForAllAlliesInRange(5), [User(RefreshAuraForTarget)]), Selector([Sequence([User(FindNearestEnemy), User(PickRandomAttack), User(AttackTarget), User(PickUnfriendlyAura), User(RefreshAuraForTarget)]), {}])])",
+                CHASE
+            )),
+            vec![
+                "Blesses every ally within 5 tiles",
+                "Attacks the nearest enemy",
+                "Curses the nearest enemy",
+                "Moves towards the nearest enemy",
+            ]
+        );
+        assert_eq!(
+            summary("Sequence([User(PickAura), Wrapper(ForAllAllies, [User(RefreshAuraForTarget)]), Wrapper(ForAllEnemies, [Sequence([User(PickRandomAttack), User(AttackTarget)])])])"),
+            vec!["Spreads its aura to every ally", "Attacks every enemy"]
+        );
+        assert!(summary("Sequence([])").is_empty());
+        assert_eq!(
+            summary("EveryNTurns(3, Sequence([User(FindWeakestEnemy), User(AttackTarget)]))"),
+            vec!["Attacks the weakest enemy every 3 turns"]
+        );
+    }
+}
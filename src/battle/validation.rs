@@ -0,0 +1,507 @@
+use std::{borrow::Borrow, fmt};
+
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+use super::{resolve_brain, SummonBehaviors};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrainWarning {
+    // Node labels from the root down, e.g. "Selector > Sequence#1 > AttackTarget#2".
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for BrainWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+pub fn validate_brain(brain: &CharacterBrainDef) -> Vec<BrainWarning> {
+    let mut validator = BrainValidator::default();
+    validator.collect_keys(brain);
+    // An empty root is how a brain says "do nothing", e.g. death.brain.
+    if !children(brain).is_empty() || matches!(brain, UnpoweredTreeDef::User(_)) {
+        validator.visit(brain, brain.label(), Picks::default());
+    }
+    validator.warnings
+}
+
+pub fn validate_brains_on_load(
+    brains: Res<BrainAssets>,
+    brain_defs: Res<Assets<CharacterBrainDef>>,
+) {
+    for (name, handle) in brains.brains.iter() {
+        let name: &str = name.borrow();
+        match resolve_brain(handle.id(), &brains, &brain_defs) {
+            Ok(brain) => {
+                for warning in validate_brain(&brain) {
+                    warn!("brains/{}.brain: {}", name, warning);
+                }
+            }
+            Err(err) => warn!("brains/{}.brain: {}", name, err),
+        }
+    }
+}
+
+// What the controller may be holding by the time a node runs.
+#[derive(Debug, Clone, Copy, Default)]
+struct Picks {
+    target: bool,
+    attack: bool,
+    movement: bool,
+    aura: bool,
+}
+
+impl Picks {
+    fn union(self, other: Picks) -> Picks {
+        Picks {
+            target: self.target || other.target,
+            attack: self.attack || other.attack,
+            movement: self.movement || other.movement,
+            aura: self.aura || other.aura,
+        }
+    }
+}
+
+#[derive(Default)]
+struct BrainValidator {
+    flags: HashSet<String>,
+    memories: HashSet<String>,
+    warnings: Vec<BrainWarning>,
+}
+
+impl BrainValidator {
+    fn warn(&mut self, path: &str, message: impl Into<String>) {
+        self.warnings.push(BrainWarning {
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn collect_keys(&mut self, node: &CharacterBrainDef) {
+        match node {
+            UnpoweredTreeDef::User(SummonBehaviors::SetFlag(key, true)) => {
+                self.flags.insert(key.clone());
+            }
+            UnpoweredTreeDef::User(SummonBehaviors::RememberTarget(key)) => {
+                self.memories.insert(key.clone());
+            }
+            _ => {}
+        }
+        for child in children(node) {
+            self.collect_keys(child);
+        }
+    }
+
+    fn visit(&mut self, node: &CharacterBrainDef, path: String, picks: Picks) -> Picks {
+        match node {
+            UnpoweredTreeDef::Sequence(nodes) => {
+                if nodes.is_empty() {
+                    self.warn(&path, "empty Sequence always completes without acting");
+                }
+                self.visit_in_order(nodes, &path, picks, never_completes, "never completes")
+            }
+            UnpoweredTreeDef::Selector(nodes) => {
+                if nodes.is_empty() {
+                    self.warn(&path, "empty Selector always fails");
+                }
+                self.visit_in_order(nodes, &path, picks, never_fails, "never fails")
+            }
+            UnpoweredTreeDef::Executor(nodes) => {
+                if nodes.is_empty() {
+                    self.warn(&path, "empty Executor always fails");
+                }
+                self.visit_in_order(nodes, &path, picks, |_| false, "")
+            }
+            UnpoweredTreeDef::UtilitySelector(scored) => {
+                if scored.is_empty() {
+                    self.warn(&path, "empty UtilitySelector always fails");
+                }
+                let nodes = scored.iter().map(|(_, node)| (1, node));
+                self.visit_any_order(nodes, &path, picks)
+            }
+            UnpoweredTreeDef::RandomSelector(nodes) => {
+                if nodes.is_empty() {
+                    self.warn(&path, "empty RandomSelector always fails");
+                }
+                self.visit_any_order(nodes.iter().map(|node| (1, node)), &path, picks)
+            }
+            UnpoweredTreeDef::WeightedSelector(weighted) => {
+                if weighted.is_empty() {
+                    self.warn(&path, "empty WeightedSelector always fails");
+                }
+                let nodes = weighted.iter().map(|(weight, node)| (*weight, node));
+                self.visit_any_order(nodes, &path, picks)
+            }
+            UnpoweredTreeDef::Repeat(node, 0) => {
+                let path = child_path(&path, node, 0);
+                self.warn(&path, "unreachable: Repeat with 0 repeats never runs it");
+                picks
+            }
+            UnpoweredTreeDef::RepeatUntilSuccess(node) => {
+                if never_completes(node) {
+                    self.warn(&path, "loops forever: its child never completes");
+                }
+                self.visit(node, child_path(&path, node, 0), picks)
+            }
+            UnpoweredTreeDef::RepeatUntilFail(node) => {
+                if never_fails(node) {
+                    self.warn(&path, "loops forever: its child never fails");
+                }
+                self.visit(node, child_path(&path, node, 0), picks)
+            }
+            UnpoweredTreeDef::Repeat(node, _)
+            | UnpoweredTreeDef::Succeeder(node)
+            | UnpoweredTreeDef::Failer(node)
+            | UnpoweredTreeDef::Inverter(node)
+            | UnpoweredTreeDef::Cooldown(_, node)
+            | UnpoweredTreeDef::Once(node)
+            | UnpoweredTreeDef::EveryNTurns(_, node)
+            | UnpoweredTreeDef::WindUp(_, node)
+            | UnpoweredTreeDef::Channel(_, node) => {
+                self.visit(node, child_path(&path, node, 0), picks)
+            }
+            UnpoweredTreeDef::User(behavior) => self.visit_behavior(behavior, &path, picks),
+            UnpoweredTreeDef::Wrapper(_, nodes) => {
+                let Some((wrapped, ignored)) = nodes.split_last() else {
+                    self.warn(&path, "wrapper has nothing to run");
+                    return picks;
+                };
+                for (index, node) in ignored.iter().enumerate() {
+                    let path = child_path(&path, node, index);
+                    self.warn(&path, "unreachable: wrappers only run their last child");
+                }
+                // Every wrapper picks each unit in turn as the target.
+                let wrapped_picks = Picks {
+                    target: true,
+                    ..picks
+                };
+                let path = child_path(&path, wrapped, ignored.len());
+                picks.union(self.visit(wrapped, path, wrapped_picks))
+            }
+            UnpoweredTreeDef::Include(name) => {
+                let unresolved = IncludeError::Unresolved(name.clone());
+                self.warn(&path, unresolved.to_string());
+                picks
+            }
+        }
+    }
+
+    fn visit_in_order(
+        &mut self,
+        nodes: &[CharacterBrainDef],
+        path: &str,
+        mut picks: Picks,
+        blocks_later: fn(&CharacterBrainDef) -> bool,
+        reason: &str,
+    ) -> Picks {
+        for (index, node) in nodes.iter().enumerate() {
+            let node_path = child_path(path, node, index);
+            if index > 0 && blocks_later(&nodes[index - 1]) {
+                self.warn(
+                    &node_path,
+                    format!("unreachable: the sibling before it {}", reason),
+                );
+                break;
+            }
+            picks = self.visit(node, node_path, picks);
+        }
+        picks
+    }
+
+    fn visit_any_order<'a>(
+        &mut self,
+        nodes: impl Iterator<Item = (u32, &'a CharacterBrainDef)>,
+        path: &str,
+        picks: Picks,
+    ) -> Picks {
+        let mut out = picks;
+        for (index, (weight, node)) in nodes.enumerate() {
+            let node_path = child_path(path, node, index);
+            if weight == 0 {
+                self.warn(&node_path, "unreachable: its weight is 0");
+                continue;
+            }
+            out = out.union(self.visit(node, node_path, picks));
+        }
+        out
+    }
+
+    fn visit_behavior(&mut self, behavior: &SummonBehaviors, path: &str, picks: Picks) -> Picks {
+        use SummonBehaviors::*;
+        let needs_target = matches!(
+            behavior,
+            PickValidAttack
+                | PickRandomAttack
+                | PickValidMovement
+                | PickRandomMovement
+                | CheckRange(_)
+                | AttackTarget
+                | MoveTowardsTarget
+                | MoveAwayFromTarget
+                | KeepDistance(_)
+                | Flank
+                | RefreshAuraForTarget
+                | RememberTarget(_)
+                | TargetIsTribe(_)
+                | TargetHealthBelow(_)
+        );
+        let moves = matches!(
+            behavior,
+            MoveTowardsTarget
+                | MoveAwayFromTarget
+                | KeepDistance(_)
+                | StayAdjacentToAlly(_)
+                | Flank
+        );
+        if needs_target && !picks.target {
+            self.warn(
+                path,
+                "needs a target, but nothing before it finds one (e.g. FindNearestEnemy)",
+            );
+        }
+        match behavior {
+            AttackTarget if !picks.attack && picks.movement => self.warn(
+                path,
+                "would attack with the index of a picked movement; PickValidAttack or PickRandomAttack first",
+            ),
+            AttackTarget if !picks.attack => self.warn(
+                path,
+                "needs PickValidAttack or PickRandomAttack before it",
+            ),
+            _ if moves && !picks.movement && picks.attack => self.warn(
+                path,
+                "would move with the index of a picked attack; PickValidMovement or PickRandomMovement first",
+            ),
+            _ if moves && !picks.movement => self.warn(
+                path,
+                "needs PickValidMovement or PickRandomMovement before it",
+            ),
+            RefreshAuraForTarget if !picks.aura => self.warn(
+                path,
+                "needs PickAura, PickRandomAura, PickFriendlyAura or PickUnfriendlyAura before it",
+            ),
+            CheckFlag(key) if !self.flags.contains(key) => self.warn(
+                path,
+                format!("flag \"{}\" is never set to true, so this always fails", key),
+            ),
+            RecallTarget(key) if !self.memories.contains(key) => self.warn(
+                path,
+                format!("\"{}\" is never remembered, so this always fails", key),
+            ),
+            _ => {}
+        }
+        match behavior {
+            FindNearestEnemy
+            | FindNearestAlly
+            | FindRandomEnemy
+            | FindRandomAlly
+            | FindWeakestEnemy
+            | FindStrongestEnemy
+            | FindMostThreateningEnemy
+            | FindEnemyOfTribe(_)
+            | RecallTarget(_) => Picks {
+                target: true,
+                ..picks
+            },
+            FindAllyNeedingAura => Picks {
+                target: true,
+                aura: true,
+                ..picks
+            },
+            PickValidAttack | PickRandomAttack => Picks {
+                attack: true,
+                movement: false,
+                ..picks
+            },
+            PickValidMovement | PickRandomMovement => Picks {
+                attack: false,
+                movement: true,
+                ..picks
+            },
+            PickAura | PickRandomAura | PickFriendlyAura | PickUnfriendlyAura => Picks {
+                aura: true,
+                ..picks
+            },
+            _ => picks,
+        }
+    }
+}
+
+pub fn children(node: &CharacterBrainDef) -> Vec<&CharacterBrainDef> {
+    match node {
+        UnpoweredTreeDef::Sequence(nodes)
+        | UnpoweredTreeDef::Selector(nodes)
+        | UnpoweredTreeDef::Executor(nodes)
+        | UnpoweredTreeDef::RandomSelector(nodes)
+        | UnpoweredTreeDef::Wrapper(_, nodes) => nodes.iter().collect(),
+        UnpoweredTreeDef::UtilitySelector(scored) => scored.iter().map(|(_, node)| node).collect(),
+        UnpoweredTreeDef::WeightedSelector(weighted) => {
+            weighted.iter().map(|(_, node)| node).collect()
+        }
+        UnpoweredTreeDef::Repeat(node, _)
+        | UnpoweredTreeDef::RepeatUntilSuccess(node)
+        | UnpoweredTreeDef::RepeatUntilFail(node)
+        | UnpoweredTreeDef::Succeeder(node)
+        | UnpoweredTreeDef::Failer(node)
+        | UnpoweredTreeDef::Inverter(node)
+        | UnpoweredTreeDef::Cooldown(_, node)
+        | UnpoweredTreeDef::Once(node)
+        | UnpoweredTreeDef::EveryNTurns(_, node)
+        | UnpoweredTreeDef::WindUp(_, node)
+        | UnpoweredTreeDef::Channel(_, node) => vec![node.as_ref()],
+        UnpoweredTreeDef::User(_) | UnpoweredTreeDef::Include(_) => vec![],
+    }
+}
+
+fn child_path(path: &str, child: &CharacterBrainDef, index: usize) -> String {
+    format!("{} > {}#{}", path, child.label(), index)
+}
+
+fn never_fails(node: &CharacterBrainDef) -> bool {
+    match node {
+        UnpoweredTreeDef::Sequence(nodes) => nodes.iter().all(never_fails),
+        UnpoweredTreeDef::Selector(nodes)
+        | UnpoweredTreeDef::Executor(nodes)
+        | UnpoweredTreeDef::RandomSelector(nodes) => nodes.iter().any(never_fails),
+        UnpoweredTreeDef::UtilitySelector(scored) => {
+            scored.iter().any(|(_, node)| never_fails(node))
+        }
+        UnpoweredTreeDef::WeightedSelector(weighted) => weighted
+            .iter()
+            .any(|(weight, node)| *weight > 0 && never_fails(node)),
+        UnpoweredTreeDef::Repeat(_, 0) => true,
+        UnpoweredTreeDef::Repeat(node, _)
+        | UnpoweredTreeDef::WindUp(_, node)
+        | UnpoweredTreeDef::Channel(_, node) => never_fails(node),
+        UnpoweredTreeDef::RepeatUntilSuccess(_)
+        | UnpoweredTreeDef::RepeatUntilFail(_)
+        | UnpoweredTreeDef::Succeeder(_)
+        | UnpoweredTreeDef::Wrapper(_, _) => true,
+        UnpoweredTreeDef::Failer(_)
+        | UnpoweredTreeDef::Cooldown(_, _)
+        | UnpoweredTreeDef::Once(_)
+        | UnpoweredTreeDef::EveryNTurns(_, _)
+        | UnpoweredTreeDef::Include(_) => false,
+        UnpoweredTreeDef::Inverter(node) => never_completes(node),
+        UnpoweredTreeDef::User(behavior) => matches!(
+            behavior,
+            SummonBehaviors::SetFlag(_, _) | SummonBehaviors::PickAura
+        ),
+    }
+}
+
+fn never_completes(node: &CharacterBrainDef) -> bool {
+    match node {
+        UnpoweredTreeDef::Sequence(nodes) => nodes.iter().any(never_completes),
+        UnpoweredTreeDef::Selector(nodes)
+        | UnpoweredTreeDef::Executor(nodes)
+        | UnpoweredTreeDef::RandomSelector(nodes) => nodes.iter().all(never_completes),
+        UnpoweredTreeDef::UtilitySelector(scored) => {
+            scored.iter().all(|(_, node)| never_completes(node))
+        }
+        UnpoweredTreeDef::WeightedSelector(weighted) => weighted
+            .iter()
+            .all(|(weight, node)| *weight == 0 || never_completes(node)),
+        UnpoweredTreeDef::Repeat(_, 0) => false,
+        UnpoweredTreeDef::Repeat(node, _)
+        | UnpoweredTreeDef::Cooldown(_, node)
+        | UnpoweredTreeDef::Once(node)
+        | UnpoweredTreeDef::EveryNTurns(_, node)
+        | UnpoweredTreeDef::WindUp(_, node)
+        | UnpoweredTreeDef::Channel(_, node) => never_completes(node),
+        UnpoweredTreeDef::Failer(_) => true,
+        UnpoweredTreeDef::Inverter(node) => never_fails(node),
+        UnpoweredTreeDef::RepeatUntilSuccess(_)
+        | UnpoweredTreeDef::RepeatUntilFail(_)
+        | UnpoweredTreeDef::Succeeder(_)
+        | UnpoweredTreeDef::Wrapper(_, _)
+        | UnpoweredTreeDef::User(_)
+        | UnpoweredTreeDef::Include(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> CharacterBrainDef {
+        ron::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn shipped_brains_are_valid() {
+        let mut brains = HashMap::new();
+        for entry in std::fs::read_dir("assets/brains").unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            brains.insert(name, parse(&std::fs::read_to_string(&path).unwrap()));
+        }
+        for (name, brain) in &brains {
+            let brain = brain
+                .resolve_includes(name, &|name| brains.get(name))
+                .unwrap();
+            let warnings = validate_brain(&brain);
+            assert!(warnings.is_empty(), "{}: {:?}", name, warnings);
+        }
+    }
+
+    #[test]
+    fn include_cycles_are_reported() {
+        let brains = HashMap::from([
+            ("a".to_string(), parse("Selector([Include(\"b\")])")),
+            ("b".to_string(), parse("Sequence([Include(\"a\")])")),
+        ]);
+        let find = |name: &str| brains.get(name);
+        assert_eq!(
+            brains["a"].resolve_includes("a", &find).err(),
+            Some(IncludeError::Cycle(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "a".to_string()
+            ]))
+        );
+        assert_eq!(
+            parse("Include(\"c\")").resolve_includes("d", &find).err(),
+            Some(IncludeError::Missing("c".to_string()))
+        );
+        // An unresolved tree is reported rather than built.
+        assert_eq!(
+            brains["a"].create_tree().err(),
+            Some(IncludeError::Unresolved("b".to_string()))
+        );
+        assert_eq!(validate_brain(&brains["a"]).len(), 1);
+    }
+
+    #[test]
+    fn attack_without_pick_is_reported() {
+        let warnings = validate_brain(&parse(
+            "Selector([Sequence([User(FindNearestEnemy), User(AttackTarget)])])",
+        ));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "Selector > Sequence#0 > AttackTarget#1");
+    }
+
+    #[test]
+    fn aura_without_pick_is_reported() {
+        let warnings = validate_brain(&parse(
+            "Executor([Wrapper(ForAllAllies, [User(RefreshAuraForTarget)])])",
+        ));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("PickAura"));
+    }
+
+    #[test]
+    fn unreachable_and_empty_nodes_are_reported() {
+        let warnings = validate_brain(&parse(
+            "Selector([Succeeder(User(FindNearestEnemy)), Sequence([]), Executor([])])",
+        ));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "Selector > Sequence#1");
+        let warnings = validate_brain(&parse("Executor([Selector([]), Sequence([])])"));
+        assert_eq!(warnings.len(), 2);
+    }
+}
@@ -4,6 +4,9 @@ use crate::prelude::*;
 
 pub struct BoardPlugin;
 
+// The player places summons on the rows nearest them.
+pub const PLAYER_ROWS: usize = 3;
+
 impl Plugin for BoardPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<BoardMouseState>()
@@ -102,7 +105,7 @@ fn setup(
             Tile {
                 x,
                 y,
-                can_place: y <= 2,
+                can_place: y < PLAYER_ROWS,
                 sprite: *tile,
             },
         ));
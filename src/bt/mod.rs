@@ -1,3 +0,0 @@
-pub mod unpowered;
-pub use unpowered::*;
-// Pulled in from another project!
@@ -1,6 +0,0 @@
-mod funcs;
-mod nodes;
-mod tree_def;
-pub use funcs::*;
-pub use nodes::*;
-pub use tree_def::*;
@@ -63,11 +63,7 @@ pub fn spawn_overlay(
                     parent.spawn((
                         TextBundle {
                             text: Text::from_section(
-                                if victory {
-                                    "Victory!".to_string()
-                                } else {
-                                    "Defeat!".to_string()
-                                },
+                                "",
                                 TextStyle {
                                     font: Default::default(),
                                     font_size: 40.0,
@@ -78,6 +74,11 @@ pub fn spawn_overlay(
                         },
                         Class::new("game_over__text"),
                         GameOverActor::Text,
+                        LocalizedText(if victory {
+                            "game_over.victory"
+                        } else {
+                            "game_over.defeat"
+                        }),
                     ));
                 });
             if victory {
@@ -99,17 +100,20 @@ pub fn add_restart_button(parent: &mut ChildBuilder) {
             Class::new("restart"),
         ))
         .with_children(|parent| {
-            parent.spawn((TextBundle {
-                text: Text::from_section(
-                    "Restart".to_string(),
-                    TextStyle {
-                        font: Default::default(),
-                        font_size: 32.,
-                        color: Color::BLACK,
-                    },
-                ),
-                ..default()
-            },));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: Default::default(),
+                            font_size: 32.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..default()
+                },
+                LocalizedText("game_over.restart"),
+            ));
         });
 }
 
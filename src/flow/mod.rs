@@ -1,4 +1,7 @@
-use crate::{prelude::*, summoner::NextWave};
+use crate::{
+    prelude::*,
+    summoner::{naming_preset, NextWave},
+};
 
 pub struct StoryPlugin;
 
@@ -18,7 +21,10 @@ impl Plugin for StoryPlugin {
             .add_systems(OnExit(GameState::Loading), start_story)
             .add_systems(
                 Update,
-                (queue_next_wave, update_narration_box, advance_narration)
+                (
+                    (queue_next_wave, advance_narration).run_if(not(naming_preset)),
+                    update_narration_box,
+                )
                     .run_if(in_state(GameState::Summoning)),
             )
             .add_systems(OnExit(GameState::Battling), check_for_game_over)
@@ -59,9 +65,7 @@ fn show_next_wave(
         next_wave.0 = wave.clone();
         for ((x, y), summon) in wave.iter() {
             let summon_type = summons
-                .npc_summons
-                .get(&*summon.to_string())
-                .or_else(|| summons.player_summons.get(&*summon.to_string()))
+                .summon_handle(summon)
                 .and_then(|handle| summon_types.get(handle))
                 .unwrap()
                 .clone();
@@ -9,15 +9,16 @@ pub struct NarrationBox;
 pub struct NarrationText(pub f32, pub String);
 
 impl NarrationText {
+    // Counted in characters rather than bytes, so non-Latin narration types out evenly.
     pub fn is_done(&self, full_string: &String) -> bool {
-        self.1.len() >= full_string.len()
+        self.1.chars().count() >= full_string.chars().count()
     }
 
     pub fn update(&mut self, delta: f32, full_string: &String) -> Option<usize> {
-        if self.1.len() < full_string.len() {
+        if !self.is_done(full_string) {
             self.0 += delta;
             let index = (self.0 / 0.05) as usize;
-            let changed = index != self.1.len();
+            let changed = index != self.1.chars().count();
             self.1 = full_string.chars().take(index).collect();
             if changed {
                 Some(index)
@@ -95,9 +96,13 @@ pub fn update_narration_box(
     time: Res<Time>,
     story_beat: Res<StoryBeat>,
     sounds: Res<AudioAssets>,
+    localization: Res<Localization>,
 ) {
     for (mut narrative, mut text) in query.iter_mut() {
-        if let Some(active_narration) = story_beat.get_active_narration() {
+        if let Some(active_narration) = story_beat
+            .get_active_narration()
+            .map(|narration| localization.text(narration))
+        {
             if let Some(change) = narrative.update(time.delta_seconds(), &active_narration) {
                 if change % 2 == 0 {
                     commands.spawn(AudioBundle {
@@ -122,6 +127,7 @@ pub fn advance_narration(
     keys: Res<ButtonInput<KeyCode>>,
     mut text_query: Query<&mut NarrationText>,
     mut story_beat: ResMut<StoryBeat>,
+    localization: Res<Localization>,
 ) {
     let mut advance_full = false;
     for (interaction, _) in interactions.iter() {
@@ -134,7 +140,10 @@ pub fn advance_narration(
         advance_full = true;
     }
     if advance_full {
-        if let Some(active_narration) = story_beat.get_active_narration().cloned() {
+        if let Some(active_narration) = story_beat
+            .get_active_narration()
+            .map(|narration| localization.text(narration))
+        {
             for mut text in text_query.iter_mut() {
                 if text.is_done(&active_narration) {
                     text.clear();
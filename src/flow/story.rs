@@ -1,6 +1,11 @@
 use bevy::{asset::AssetPath, log::tracing_subscriber::fmt::time, utils::Uuid};
 
-use crate::{battle::DeathCharacterBrain, persistence::SaveData, prelude::*, summoner::NextWave};
+use crate::{
+    battle::{resolve_brain, DeathCharacterBrain},
+    persistence::SaveData,
+    prelude::*,
+    summoner::{ArmySynergies, Formation, Formations, NextWave},
+};
 
 #[derive(Serialize, Deserialize, Default, Resource, Asset, TypePath, Clone)]
 pub struct Story {
@@ -102,18 +107,18 @@ impl Default for StoryBeat {
     fn default() -> Self {
         Self {
             mana_gained: 0,
-            narration: vec![
-                "Welcome to Summoner's Chess!\nSave the town and help reclaim the land with your magic!".to_string(),
-                "You've already selected your first summon, which you can see to the right"
-                    .to_string(),
-                "You can click on the summon or press the hotkey to select it".to_string(),
-                "Then click on the board or press the hotkey to place it".to_string(),
-                "When you have finished placing your summons, press Enter to start the battle"
-                    .to_string(),
-                "When the battle starts, enemies will spawn on the opposite side of the board"
-                    .to_string(),
-                "Your creatures have it from there, Summoner! When the battle is done, maybe you'll have learned a few things...".to_string(),
-            ],
+            // Locale keys, like the narration in story.teller.
+            narration: [
+                "tutorial.welcome",
+                "tutorial.first_summon",
+                "tutorial.select",
+                "tutorial.place",
+                "tutorial.start_battle",
+                "tutorial.enemies",
+                "tutorial.learn",
+            ]
+            .map(String::from)
+            .to_vec(),
             victory: false,
             defeat: false,
         }
@@ -176,7 +181,9 @@ pub fn restart_on_click(
     mut story_beat: ResMut<StoryBeat>,
     mut mana: ResMut<Mana>,
     mut known_summons: ResMut<KnownSummons>,
+    mut veterans: ResMut<Veterans>,
     mut save_data: ResMut<SaveData>,
+    mut formations: ResMut<Formations>,
 ) {
     for interaction in interactions.iter() {
         if *interaction == Interaction::Pressed {
@@ -184,7 +191,9 @@ pub fn restart_on_click(
             *story_beat = StoryBeat::default();
             *mana = Mana::default();
             *known_summons = KnownSummons::default();
+            *veterans = Veterans::default();
             save_data.armies.clear();
+            formations.last = Formation::default();
         }
     }
 }
@@ -202,6 +211,7 @@ pub fn queue_next_wave(
     summon_types: Res<Assets<SummonType>>,
     summons: Res<SummonsAssets>,
     my_minions: Res<SummonedMinions>,
+    veterans: Res<Veterans>,
     sounds: Res<AudioAssets>,
 ) {
     if keys.just_pressed(KeyCode::Enter) && !story_beat.narrating() {
@@ -213,6 +223,7 @@ pub fn queue_next_wave(
             return;
         }
         let mut short_army = my_minions.clone();
+        short_army.set_veterans(veterans.clone());
         short_army.compress(&summons, &summon_types);
         save.armies.push(short_army);
         let wave = core::mem::take(&mut next_wave.0);
@@ -234,6 +245,8 @@ pub fn spawn_all_summons(
     mut my_minions: ResMut<SummonedMinions>,
     mut enemy_minions: ResMut<EnemyMinions>,
     known_summons: Res<KnownSummons>,
+    veterans: Res<Veterans>,
+    army_synergies: Res<ArmySynergies>,
     summons: Res<SummonsAssets>,
     textures: Res<TextureAssets>,
     brains: Res<BrainAssets>,
@@ -255,37 +268,54 @@ pub fn spawn_all_summons(
         progress.0 = 0.;
         return;
     };
-    let summon_type = if faction == Faction::Player {
-        known_summons.get(&summon)
+    let (summon_type, experience) = if faction == Faction::Player {
+        (known_summons.get(&summon), veterans.experience(&summon))
     } else {
-        let summon_handle = summons
-            .npc_summons
-            .get(&*summon)
-            .or_else(|| summons.player_summons.get(&*summon))
-            .unwrap();
-        summon_assets.get(summon_handle).unwrap().clone()
+        let summon_handle = summons.summon_handle(&summon).unwrap();
+        (
+            summon_assets.get(summon_handle).unwrap().clone(),
+            enemy_minions.0.veterans().experience(&summon),
+        )
+    };
+    // A missing brain or bad Include skips the unit rather than ending the game.
+    let build = |brain: Option<Handle<CharacterBrainDef>>, name: &str| {
+        let brain = brain.ok_or_else(|| IncludeError::Missing(name.to_string()))?;
+        let brain_def = resolve_brain(brain.id(), &brains, &brain_assets)?;
+        CharacterBrain::new(brain.id(), &brain_def)
     };
-    let summoned = spawn_summon(&mut commands, &textures, summon_type.clone(), x, y, true);
-
-    let brain = summon_type.get_brain(&brains).unwrap();
-    let brain_def = brain_assets.get(brain).unwrap();
-
     let death_brain = summon_type
         .get_death_brain(&brains)
-        .unwrap_or(brains.brains.get("death").unwrap().clone());
-    let death_brain_def = brain_assets.get(death_brain).unwrap();
+        .or_else(|| brains.brains.get("death").cloned());
+    let (brain, death_brain) = match (
+        build(summon_type.get_brain(&brains), summon_type.brain()),
+        build(death_brain, "death"),
+    ) {
+        (Ok(brain), Ok(death_brain)) => (brain, death_brain),
+        (Err(err), _) | (_, Err(err)) => {
+            warn!(
+                "Skipped {} at ({}, {}), its brain could not be built: {}",
+                summon_type.name(),
+                x,
+                y,
+                err
+            );
+            return;
+        }
+    };
+
+    let summoned = spawn_summon(&mut commands, &textures, summon_type.clone(), x, y, true);
+    let mut stats = summon_type.veteran_stats(experience);
+    for bonus in army_synergies.bonuses(&faction, &summon_type.tribe) {
+        stats.apply_aura(bonus.clone());
+    }
 
-    commands.spawn(AudioBundle {
-        source: sounds
-            .summon_stings
-            .get(summon_type.tribe.sting())
-            .unwrap()
-            .clone(),
-        ..Default::default()
-    });
-    commands.entity(summoned).insert((
-        CharacterBrain::new(brain_def),
-        DeathCharacterBrain(CharacterBrain::new(death_brain_def)),
-        faction,
-    ));
+    if let Some(source) = sounds.summon_sound(&summon_type, SoundCue::Summon) {
+        commands.spawn(AudioBundle {
+            source,
+            ..Default::default()
+        });
+    }
+    commands
+        .entity(summoned)
+        .insert((stats, brain, DeathCharacterBrain(death_brain), faction));
 }
@@ -7,6 +7,7 @@ impl Plugin for GamePlugin {
             .add_plugins(EcssPlugin::default())
             .add_plugins(crate::menu::MenuPlugin)
             .add_plugins(crate::loading::LoadingPlugin)
+            .add_plugins(crate::locale::LocalizationPlugin)
             .add_plugins(crate::board::BoardPlugin)
             .add_plugins(crate::battle::BattlePlugin)
             .add_plugins(crate::summoner::SummonerPlugin)
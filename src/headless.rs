@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use crate::{
+    battle::SimulatedUnit,
+    loading::{ContentEntry, ContentManifest, CONTENT_FOLDERS, CONTENT_MANIFEST},
+    locale::ENGLISH,
+    mods::{content_file, read_content, ActivePacks},
+    prelude::*,
+    summoner::{Fusion, Synergies},
+};
+
+// The game's assets, read straight from disk for tools that run without an App.
+// Everything is keyed by file stem, the way the loaded asset collections are.
+pub struct HeadlessAssets {
+    pub player_summons: HashMap<String, SummonType>,
+    pub npc_summons: HashMap<String, SummonType>,
+    pub fused_summons: HashMap<String, SummonType>,
+    // Sorted by name, like waves.
+    pub fusions: Vec<(String, Fusion)>,
+    pub brains: HashMap<String, CharacterBrainDef>,
+    // Sorted by name, so tools see them in the same order every run.
+    pub waves: Vec<(String, SummonedMinions)>,
+    pub story: Story,
+    pub synergies: Synergies,
+    // File stems of the listed sounds, which are only played, never parsed.
+    pub sounds: Vec<String>,
+    // In English, with every listed locale available.
+    pub localization: Localization,
+    // Content files on disk that the manifest leaves out, so the game never loads them.
+    pub unlisted: Vec<String>,
+    pub packs: ActivePacks,
+}
+
+impl HeadlessAssets {
+    // Reads the files listed in the content manifest, with mod packs applied, like the
+    // Loading state does.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let (manifest, packs) = read_content(root)?;
+        let files = |key: &str| match manifest.0.get(key) {
+            Some(ContentEntry::Files { paths }) => Ok(paths.as_slice()),
+            _ => Err(format!("{}: missing \"{}\"", CONTENT_MANIFEST, key)),
+        };
+        let unlisted = unlisted_files(root, &manifest)?;
+        fn read_file<T: for<'de> Deserialize<'de>>(
+            root: &Path,
+            manifest: &ContentManifest,
+            key: &str,
+        ) -> Result<T, String> {
+            let Some(ContentEntry::File { path }) = manifest.0.get(key) else {
+                return Err(format!("{}: missing \"{}\"", CONTENT_MANIFEST, key));
+            };
+            let source = std::fs::read_to_string(content_file(root, path))
+                .map_err(|err| format!("{}: {}", path, err))?;
+            ron::from_str(&source).map_err(|err| format!("{}: {}", path, err))
+        }
+        Ok(HeadlessAssets {
+            player_summons: read_ron(root, files("player_summons")?)?
+                .into_iter()
+                .collect(),
+            npc_summons: read_ron(root, files("npc_summons")?)?.into_iter().collect(),
+            fused_summons: read_ron(root, files("fused_summons")?)?
+                .into_iter()
+                .collect(),
+            fusions: read_ron(root, files("fusions")?)?,
+            brains: read_ron(root, files("brains")?)?.into_iter().collect(),
+            waves: read_ron(root, files("waves")?)?,
+            story: read_file(root, &manifest, "story")?,
+            synergies: read_file(root, &manifest, "synergies")?,
+            sounds: files("sounds")?
+                .iter()
+                .filter_map(|path| Path::new(path).file_stem())
+                .map(|stem| stem.to_string_lossy().to_string())
+                .collect(),
+            localization: Localization::new(
+                read_ron(root, files("locales")?)?.into_iter().collect(),
+                ENGLISH,
+            ),
+            unlisted,
+            packs,
+        })
+    }
+
+    // Waves may use any summon, in the order SummonsAssets::summon_handle looks them up.
+    pub fn summon(&self, name: &str) -> Option<&SummonType> {
+        self.npc_summons
+            .get(name)
+            .or_else(|| self.player_summons.get(name))
+            .or_else(|| self.fused_summons.get(name))
+    }
+
+    // Looks up the brain and inlines every brain it includes.
+    pub fn brain(&self, name: &str) -> Result<CharacterBrainDef, IncludeError> {
+        self.brains
+            .get(name)
+            .ok_or_else(|| IncludeError::Missing(name.to_string()))?
+            .resolve_includes(name, &|name| self.brains.get(name))
+    }
+
+    // A unit as spawn_all_summons would spawn it, with its brain swapped out if one is given.
+    pub fn unit(
+        &self,
+        summon: &str,
+        faction: Faction,
+        position: (usize, usize),
+        brain: Option<&CharacterBrainDef>,
+    ) -> Result<SimulatedUnit, String> {
+        let summon_type = self
+            .summon(summon)
+            .ok_or_else(|| format!("unknown summon {}", summon))?;
+        let find = |name: &str| {
+            self.brain(name)
+                .map_err(|err| format!("{}: {}", summon, err))
+        };
+        let brain = match brain {
+            Some(brain) => brain.clone(),
+            None => find(summon_type.brain())?,
+        };
+        let death_brain = match summon_type.death_brain() {
+            "" => find("death")?,
+            name => find(name)?,
+        };
+        Ok(SimulatedUnit {
+            faction,
+            position,
+            stats: summon_type.clone().into(),
+            brain: CharacterBrain::new(AssetId::default(), &brain)
+                .map_err(|err| format!("{}: {}", summon, err))?,
+            death_brain: CharacterBrain::new(AssetId::default(), &death_brain)
+                .map_err(|err| format!("{}: {}", summon, err))?,
+            removed: false,
+        })
+    }
+}
+
+// Content files on disk that the manifest leaves out, sorted.
+pub fn unlisted_files(root: &Path, manifest: &ContentManifest) -> Result<Vec<String>, String> {
+    let mut unlisted = vec![];
+    for (key, folder, extension) in CONTENT_FOLDERS {
+        let Some(ContentEntry::Files { paths }) = manifest.0.get(key) else {
+            return Err(format!("{}: missing \"{}\"", CONTENT_MANIFEST, key));
+        };
+        let entries =
+            std::fs::read_dir(root.join(folder)).map_err(|err| format!("{}: {}", folder, err))?;
+        for entry in entries {
+            let path = entry.map_err(|err| err.to_string())?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(extension) {
+                continue;
+            }
+            let file = format!("{}/{}", folder, path.file_name().unwrap().to_string_lossy());
+            // A pack replacing the file doesn't make it unlisted.
+            let stem = path.file_stem();
+            if !paths
+                .iter()
+                .any(|listed| Path::new(listed).file_stem() == stem)
+            {
+                unlisted.push(file);
+            }
+        }
+    }
+    unlisted.sort();
+    Ok(unlisted)
+}
+
+// The listed files, sorted by file stem.
+fn read_ron<T: for<'de> Deserialize<'de>>(
+    root: &Path,
+    paths: &[String],
+) -> Result<Vec<(String, T)>, String> {
+    let mut values = vec![];
+    for file in paths {
+        let path = content_file(root, file);
+        let source = std::fs::read_to_string(&path).map_err(|err| format!("{}: {}", file, err))?;
+        let value = ron::from_str(&source).map_err(|err| format!("{}: {}", file, err))?;
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        values.push((name, value));
+    }
+    values.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(values)
+}
@@ -1,63 +1,159 @@
-use crate::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::headless::unlisted_files;
+use crate::{
+    locale::Locale,
+    mods::{read_content, ActivePacks},
+    prelude::*,
+    summoner::{Fusion, Synergies, SYNERGIES_FILE},
+};
 
 pub struct LoadingPlugin;
 
+// Lists every summon, wave, brain, sound, locale, story and synergy file, so new content needs
+// no code changes.
+// Files the manifest is missing are warned about on startup and by `cargo run -- check-assets`.
+pub const CONTENT_MANIFEST: &str = "dynamic/content.assets.ron";
+pub const STORY_FILE: &str = "story.teller";
+
+// Manifest key, folder and file extension of each kind of content.
+pub const CONTENT_FOLDERS: [(&str, &str, &str); 8] = [
+    ("player_summons", "summons", "summon"),
+    ("npc_summons", "npc", "summon"),
+    ("fused_summons", "fused", "summon"),
+    ("fusions", "fusions", "fusion"),
+    ("waves", "waves", "wave"),
+    ("brains", "brains", "brain"),
+    ("sounds", "sounds", "wav"),
+    ("locales", "locales", "locale"),
+];
+
+// Manifest key and path of each kind of content that is a single file.
+pub const CONTENT_FILES: [(&str, &str); 2] = [("story", STORY_FILE), ("synergies", SYNERGIES_FILE)];
+
+// The subset of bevy_asset_loader's StandardDynamicAssetCollection format the manifest uses.
+#[derive(Deserialize)]
+pub struct ContentManifest(pub HashMap<String, ContentEntry>);
+
+#[derive(Deserialize)]
+pub enum ContentEntry {
+    File { path: String },
+    Files { paths: Vec<String> },
+}
+
+impl ContentManifest {
+    pub fn read(assets_root: &std::path::Path) -> Result<Self, String> {
+        let source = std::fs::read_to_string(assets_root.join(CONTENT_MANIFEST))
+            .map_err(|err| format!("{}: {}", CONTENT_MANIFEST, err))?;
+        ron::from_str(&source).map_err(|err| format!("{}: {}", CONTENT_MANIFEST, err))
+    }
+}
+
+impl From<ContentEntry> for StandardDynamicAsset {
+    fn from(entry: ContentEntry) -> Self {
+        match entry {
+            ContentEntry::File { path } => StandardDynamicAsset::File { path },
+            ContentEntry::Files { paths } => StandardDynamicAsset::Files { paths },
+        }
+    }
+}
+
 /// This plugin loads all assets using [`AssetLoader`] from a third party bevy plugin
 /// Alternatively you can write the logic to load assets yourself
 /// If interested, take a look at <https://bevy-cheatbook.github.io/features/assets.html>
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_loading_state(
-            LoadingState::new(GameState::Loading)
-                .continue_to_state(GameState::Menu)
-                .load_collection::<AudioAssets>()
-                .load_collection::<StyleAssets>()
-                .load_collection::<TextureAssets>()
-                .load_collection::<SummonsAssets>()
-                .load_collection::<BrainAssets>(),
-        )
-        .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<SummonType>::new(
-            &["summon"],
-        ))
-        .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<Story>::new(&[
-            "teller",
-        ]))
-        .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<SummonedMinions>::new(&["wave"]))
-        .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<CharacterBrainDef>::new(&["brain"]));
+        let loading_state =
+            LoadingState::new(GameState::Loading).continue_to_state(GameState::Menu);
+        // The web build can't see mods/, so it loads the manifest as it is.
+        #[cfg(target_arch = "wasm32")]
+        let loading_state = loading_state
+            .with_dynamic_assets_file::<StandardDynamicAssetCollection>(CONTENT_MANIFEST);
+        #[cfg(not(target_arch = "wasm32"))]
+        register_content(app);
+        app.init_resource::<ActivePacks>()
+            .add_loading_state(
+                loading_state
+                    .load_collection::<AudioAssets>()
+                    .load_collection::<StyleAssets>()
+                    .load_collection::<TextureAssets>()
+                    .load_collection::<SummonsAssets>()
+                    .load_collection::<LocaleAssets>()
+                    .load_collection::<BrainAssets>(),
+            )
+            .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<SummonType>::new(
+                &["summon"],
+            ))
+            .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<Story>::new(&[
+                "teller",
+            ]))
+            .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<Synergies>::new(
+                &["synergies"],
+            ))
+            .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<Fusion>::new(&[
+                "fusion",
+            ]))
+            .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<Locale>::new(&[
+                "locale",
+            ]))
+            .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<SummonedMinions>::new(&["wave"]))
+            .add_plugins(
+                bevy_common_assets::ron::RonAssetPlugin::<CharacterBrainDef>::new(&["brain"]),
+            );
     }
 }
 
+// Reads the manifest up front rather than through the Loading state, so mod packs can
+// replace its entries before anything loads.
+#[cfg(not(target_arch = "wasm32"))]
+fn register_content(app: &mut App) {
+    let assets_root = bevy::asset::io::file::FileAssetReader::get_base_path().join("assets");
+    // Every collection needs the manifest's keys, so there's nothing to load without it.
+    let (manifest, active) = match read_content(&assets_root) {
+        Ok(content) => content,
+        Err(err) => {
+            error!("Could not read content: {}", err);
+            std::process::exit(1);
+        }
+    };
+    for pack in &active.packs {
+        info!("Loading mod pack {}", pack);
+    }
+    for skipped in &active.skipped {
+        warn!("Skipping mod pack {}", skipped);
+    }
+    for conflict in &active.conflicts {
+        warn!("Mod pack conflict: {}", conflict);
+    }
+    for replaced in &active.overrides {
+        info!("Mod pack override: {}", replaced);
+    }
+    match unlisted_files(&assets_root, &manifest) {
+        Ok(unlisted) => {
+            for file in unlisted {
+                warn!(
+                    "{} is not listed in {}, so it is never loaded",
+                    file, CONTENT_MANIFEST
+                );
+            }
+        }
+        Err(err) => warn!("Could not look for unlisted content: {}", err),
+    }
+    app.init_resource::<DynamicAssets>();
+    let mut dynamic_assets = app.world.resource_mut::<DynamicAssets>();
+    for (key, entry) in manifest.0 {
+        dynamic_assets.register_asset(key, Box::new(StandardDynamicAsset::from(entry)));
+    }
+    app.insert_resource(active);
+}
+
 // the following asset collections will be loaded during the State `GameState::Loading`
 // when done loading, they will be inserted as resources (see <https://github.com/NiklasEi/bevy_asset_loader>)
 
 #[derive(AssetCollection, Resource)]
 pub struct AudioAssets {
-    #[asset(
-        paths(
-            "audio/angel_summon_sting.wav",
-            "audio/construct_summon_sting.wav",
-            "audio/demon_summon_sting.wav",
-            "audio/elemental_summon_sting.wav",
-            "audio/fairy_summon_sting.wav",
-            "audio/undead_summon_sting.wav",
-            "audio/enemy_summon_sting.wav"
-        ),
-        collection(typed, mapped)
-    )]
-    pub summon_stings: HashMap<AssetFileStem, Handle<AudioSource>>,
-    #[asset(
-        paths(
-            "audio/angel_death_sting.wav",
-            "audio/construct_death_sting.wav",
-            "audio/demon_death_sting.wav",
-            "audio/elemental_death_sting.wav",
-            "audio/fairy_death_sting.wav",
-            "audio/undead_death_sting.wav",
-            "audio/enemy_death_sting.wav"
-        ),
-        collection(typed, mapped)
-    )]
-    pub death_stings: HashMap<AssetFileStem, Handle<AudioSource>>,
+    // Tribe stings and summons' own sounds, from the content manifest so packs can add more.
+    #[asset(key = "sounds", collection(typed, mapped))]
+    pub sounds: HashMap<AssetFileStem, Handle<AudioSource>>,
     #[asset(path = "audio/defeat_sting.wav")]
     pub defeat_sting: Handle<AudioSource>,
     #[asset(path = "audio/victory_sting.wav")]
@@ -121,82 +217,59 @@ pub struct TextureAssets {
 
 #[derive(AssetCollection, Resource)]
 pub struct SummonsAssets {
-    #[asset(
-        paths(
-            "summons/Bane.summon",
-            "summons/Brutalizer.summon",
-            "summons/Butcher.summon",
-            "summons/Cherub.summon",
-            "summons/Ember.summon",
-            "summons/Feline.summon",
-            "summons/Ghost.summon",
-            "summons/Ghoul.summon",
-            "summons/Golem.summon",
-            "summons/Guardian.summon",
-            "summons/Pixie.summon",
-            "summons/Pylon.summon",
-            "summons/Seraph.summon",
-            "summons/Skeleton.summon",
-            "summons/Undine.summon",
-            "summons/Vampire.summon",
-            "summons/Virtue.summon",
-            "summons/Vulpine.summon",
-            "summons/Watcher.summon",
-            "summons/Wisp.summon",
-            "summons/Wolfine.summon",
-        ),
-        collection(typed, mapped)
-    )]
+    #[asset(key = "player_summons", collection(typed, mapped))]
     pub player_summons: HashMap<AssetFileStem, Handle<SummonType>>,
-    #[asset(
-        paths(
-            "npc/Bones.summon",
-            "npc/Death.summon",
-            "npc/Pain.summon",
-            "npc/Necromancer.summon",
-        ),
-        collection(typed, mapped)
-    )]
+    #[asset(key = "npc_summons", collection(typed, mapped))]
     pub npc_summons: HashMap<AssetFileStem, Handle<SummonType>>,
-    #[asset(
-        paths(
-            "waves/wave0.wave",
-            "waves/wave1.wave",
-            "waves/wave2.wave",
-            "waves/wave3.wave",
-            "waves/wave4.wave",
-            "waves/wave5.wave",
-            "waves/wave6.wave",
-            "waves/wave7.wave",
-            "waves/wave8.wave",
-            "waves/boss0.wave",
-            "waves/boss1.wave",
-        ),
-        collection(typed, mapped)
-    )]
+    #[asset(key = "fused_summons", collection(typed, mapped))]
+    pub fused_summons: HashMap<AssetFileStem, Handle<SummonType>>,
+    #[asset(key = "fusions", collection(typed, mapped))]
+    pub fusions: HashMap<AssetFileStem, Handle<Fusion>>,
+    #[asset(key = "waves", collection(typed, mapped))]
     pub waves: HashMap<AssetFileStem, Handle<SummonedMinions>>,
-    #[asset(path = "story.teller")]
+    #[asset(key = "story")]
     pub story_teller: Handle<Story>,
+    #[asset(key = "synergies")]
+    pub synergies: Handle<Synergies>,
+}
+
+impl AudioAssets {
+    // What a unit of `summon` plays for `cue`, if anything.
+    pub fn summon_sound(&self, summon: &SummonType, cue: SoundCue) -> Option<Handle<AudioSource>> {
+        match summon.sound(cue) {
+            Some(name) => self.sounds.get(name).cloned(),
+            None if cue == SoundCue::Hurt => Some(self.hurt.clone()),
+            None => None,
+        }
+    }
+}
+
+impl SummonsAssets {
+    // Any summon by name: npcs first, since waves use them most, then player and fused summons.
+    pub fn summon_handle(&self, name: &str) -> Option<&Handle<SummonType>> {
+        self.npc_summons
+            .get(name)
+            .or_else(|| self.player_summons.get(name))
+            .or_else(|| self.fused_summons.get(name))
+    }
+
+    // Summons the player can own, which rune codes name by short code.
+    pub fn owned_summons(&self) -> impl Iterator<Item = &Handle<SummonType>> {
+        self.player_summons
+            .values()
+            .chain(self.fused_summons.values())
+    }
+}
+
+// Every language in the content manifest, by language code.
+#[derive(AssetCollection, Resource)]
+pub struct LocaleAssets {
+    #[asset(key = "locales", collection(typed, mapped))]
+    pub locales: HashMap<AssetFileStem, Handle<Locale>>,
 }
 
 #[derive(AssetCollection, Resource)]
 pub struct BrainAssets {
-    #[asset(
-        paths(
-            "brains/construct.brain",
-            "brains/demon.brain",
-            "brains/death.brain",
-            "brains/draining.brain",
-            "brains/elemental_buff_death.brain",
-            "brains/evading_debuff_nearest.brain",
-            "brains/evading_prioritized.brain",
-            "brains/evading.brain",
-            "brains/fighter_prioritized.brain",
-            "brains/fighter.brain",
-            "brains/large_construct.brain",
-            "brains/necromancer.brain",
-        ),
-        collection(typed, mapped)
-    )]
+    #[asset(key = "brains", collection(typed, mapped))]
     pub brains: HashMap<AssetFileStem, Handle<CharacterBrainDef>>,
 }
@@ -0,0 +1,272 @@
+use std::fmt;
+
+use crate::{loading::LocaleAssets, persistence::Settings, prelude::*};
+
+pub const LOCALES_FOLDER: &str = "locales";
+pub const ENGLISH: &str = "en";
+
+// locales/<language>.locale. Keys a locale leaves out fall back to English.
+#[derive(Serialize, Deserialize, Asset, TypePath, Clone, Debug, Default)]
+pub struct Locale {
+    // What the language calls itself, for the language button.
+    pub name: String,
+    // A font covering the locale's script, for scripts the default font lacks.
+    #[serde(default)]
+    pub font: Option<String>,
+    pub strings: HashMap<String, String>,
+}
+
+impl Locale {
+    #[cfg(test)]
+    pub fn of(strings: &[(&str, &str)]) -> Self {
+        Self {
+            strings: strings
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Localization {
+    // By language code, which is the locale's file stem.
+    locales: HashMap<String, Locale>,
+    language: String,
+}
+
+impl Localization {
+    pub fn new(locales: HashMap<String, Locale>, language: &str) -> Self {
+        let mut localization = Self {
+            locales,
+            language: ENGLISH.to_string(),
+        };
+        localization.set_language(language);
+        localization
+    }
+
+    // English alone, holding just the given strings, for tests that shouldn't read locales/.
+    #[cfg(test)]
+    pub fn english(strings: &[(&str, &str)]) -> Self {
+        Self::new(
+            HashMap::from([(ENGLISH.to_string(), Locale::of(strings))]),
+            ENGLISH,
+        )
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    // Unknown languages leave the current one in place.
+    pub fn set_language(&mut self, language: &str) {
+        if self.locales.contains_key(language) {
+            self.language = language.to_string();
+        }
+    }
+
+    // Language codes, sorted, for cycling through.
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<_> = self.locales.keys().map(|code| code.as_str()).collect();
+        languages.sort();
+        languages
+    }
+
+    // (language, key) for strings English lacks, which nothing ever looks up.
+    pub fn unknown_keys(&self) -> Vec<(&str, &str)> {
+        let english = self.locales.get(ENGLISH);
+        let mut unknown = vec![];
+        for language in self.languages() {
+            for key in self.locales[language].strings.keys() {
+                if !english.is_some_and(|english| english.strings.contains_key(key)) {
+                    unknown.push((language, key.as_str()));
+                }
+            }
+        }
+        unknown.sort();
+        unknown
+    }
+
+    pub fn locale(&self) -> Option<&Locale> {
+        self.locales.get(&self.language)
+    }
+
+    // The active language's string, else English's.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.locale()
+            .and_then(|locale| locale.strings.get(key))
+            .or_else(|| {
+                self.locales
+                    .get(ENGLISH)
+                    .and_then(|locale| locale.strings.get(key))
+            })
+            .map(|text| text.as_str())
+    }
+
+    // Falls back to the key itself, so story files and mods can use plain text too.
+    pub fn text(&self, key: &str) -> String {
+        self.get(key).unwrap_or(key).to_string()
+    }
+
+    // The string with each {name} replaced by its argument.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut text = self.text(key);
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+
+    // Content names and taglines are their own fallback, so only translations need keys.
+    pub fn content(&self, key: &str, fallback: &str) -> String {
+        self.get(key).unwrap_or(fallback).to_string()
+    }
+}
+
+// A text whose first section is the string for the key, refreshed when the language changes.
+#[derive(Component)]
+pub struct LocalizedText(pub &'static str);
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Localization>()
+            .add_systems(OnExit(GameState::Loading), load_locales)
+            .add_systems(Update, (refresh_localized_text, apply_locale_font));
+    }
+}
+
+pub fn load_locales(
+    mut localization: ResMut<Localization>,
+    locale_assets: Res<LocaleAssets>,
+    locales: Res<Assets<Locale>>,
+    settings: Res<Settings>,
+) {
+    let locales = locale_assets
+        .locales
+        .iter()
+        .map(|(language, handle)| {
+            let language: &str = std::borrow::Borrow::borrow(language);
+            (language.to_string(), locales.get(handle).unwrap().clone())
+        })
+        .collect();
+    *localization = Localization::new(locales, &settings.language);
+}
+
+pub fn refresh_localized_text(
+    localization: Res<Localization>,
+    mut texts: Query<(&mut Text, Ref<LocalizedText>)>,
+) {
+    for (mut text, key) in texts.iter_mut() {
+        if localization.is_changed() || key.is_added() {
+            text.sections[0].value = localization.text(key.0);
+        }
+    }
+}
+
+// Text is drawn with the default font handle, so a locale's font takes that handle's place.
+// The original default is kept to put back when switching to a locale without a font.
+pub fn apply_locale_font(
+    localization: Res<Localization>,
+    asset_server: Res<AssetServer>,
+    mut fonts: ResMut<Assets<Font>>,
+    mut applied: Local<Option<String>>,
+    mut pending: Local<Option<Handle<Font>>>,
+    mut original: Local<Option<Font>>,
+    mut texts: Query<&mut Text>,
+) {
+    let wanted = localization.locale().and_then(|locale| locale.font.clone());
+    if localization.is_changed() && wanted != *applied {
+        if original.is_none() {
+            *original = fonts.get(&Handle::<Font>::default()).cloned();
+        }
+        *applied = wanted.clone();
+        *pending = wanted.map(|path| asset_server.load(path));
+        if let (None, Some(font)) = (pending.as_ref(), original.clone()) {
+            replace_default_font(&mut fonts, font, &mut texts);
+        }
+    }
+    if let Some(font) = pending.as_ref().and_then(|font| fonts.get(font)).cloned() {
+        *pending = None;
+        replace_default_font(&mut fonts, font, &mut texts);
+    }
+}
+
+fn replace_default_font(fonts: &mut Assets<Font>, font: Font, texts: &mut Query<&mut Text>) {
+    // Removing first drops the glyphs cached from the old font.
+    fonts.remove(&Handle::<Font>::default());
+    fonts.insert(&Handle::<Font>::default(), font);
+    for mut text in texts.iter_mut() {
+        text.set_changed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::HeadlessAssets;
+    use std::path::Path;
+
+    #[test]
+    fn missing_strings_fall_back_to_english() {
+        let english = Locale::of(&[
+            ("game_over.victory", "Victory!"),
+            ("synergy.progress", "{tribe} {units}/{next}"),
+        ]);
+        let russian = Locale::of(&[("game_over.victory", "Победа!")]);
+        let mut localization = Localization::new(
+            HashMap::from([(ENGLISH.to_string(), english), ("ru".to_string(), russian)]),
+            "ru",
+        );
+        assert_eq!(localization.text("game_over.victory"), "Победа!");
+        // Russian leaves this pattern to English.
+        assert_eq!(
+            localization.format(
+                "synergy.progress",
+                &[("tribe", &"Нежить"), ("units", &3), ("next", &4)]
+            ),
+            "Нежить 3/4"
+        );
+        assert_eq!(localization.text("No key at all"), "No key at all");
+        assert_eq!(localization.content("summon.Ghoul", "Ghoul"), "Ghoul");
+
+        localization.set_language("xx");
+        assert_eq!(localization.language(), "ru");
+        localization.set_language(ENGLISH);
+        assert_eq!(localization.text("game_over.victory"), "Victory!");
+        assert_eq!(localization.languages(), vec!["en", "ru"]);
+
+        localization
+            .locales
+            .insert("fr".to_string(), Locale::of(&[("menu.quit", "Quitter")]));
+        assert_eq!(localization.unknown_keys(), vec![("fr", "menu.quit")]);
+    }
+
+    #[test]
+    fn shipped_narration_is_localized() {
+        let HeadlessAssets {
+            localization,
+            story,
+            ..
+        } = HeadlessAssets::load(Path::new("assets")).unwrap();
+        let narration = StoryBeat::default().narration.into_iter().chain(
+            [
+                story.winning_beats,
+                story.losing_beats,
+                story.agnostic_beats,
+            ]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|beat| match beat {
+                StoryBeatType::Narration(key) => Some(key),
+                _ => None,
+            }),
+        );
+        for key in narration {
+            assert!(localization.get(&key).is_some(), "no English for {}", key);
+        }
+    }
+}
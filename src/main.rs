@@ -2,20 +2,24 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use bevy::{
+    asset::io::AssetSource,
     window::{Window, WindowPlugin, WindowResolution},
     DefaultPlugins,
 };
 mod game;
 use game::GamePlugin;
 
+mod asset_check;
 mod battle;
 #[cfg(target_arch = "wasm32")]
 pub mod bindings;
 mod board;
-mod bt;
 mod flow;
+mod headless;
 mod loading;
+mod locale;
 mod menu;
+mod mods;
 mod persistence;
 mod prelude;
 mod state;
@@ -25,18 +29,32 @@ mod summons;
 use crate::prelude::*;
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Ludum Dare 55".to_string(),
-                canvas: Some("#bevy".to_owned()),
-                prevent_default_event_handling: false,
-                resizable: false,
-                resolution: WindowResolution::new(WINDOW_SIZE.0, WINDOW_SIZE.1),
-                ..default()
-            }),
-            ..Default::default()
-        }))
-        .add_plugins(GamePlugin)
-        .run();
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("evolve") => return battle::run_evolve(args),
+        Some("check-assets") => return asset_check::run_check_assets(args),
+        _ => {}
+    }
+    let mut app = App::new();
+    // Asset sources have to exist before the AssetPlugin is built.
+    #[cfg(not(target_arch = "wasm32"))]
+    app.register_asset_source(
+        mods::MODS_SOURCE,
+        AssetSource::build().with_reader(AssetSource::get_default_reader(
+            mods::MODS_FOLDER.to_string(),
+        )),
+    );
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "Ludum Dare 55".to_string(),
+            canvas: Some("#bevy".to_owned()),
+            prevent_default_event_handling: false,
+            resizable: false,
+            resolution: WindowResolution::new(WINDOW_SIZE.0, WINDOW_SIZE.1),
+            ..default()
+        }),
+        ..Default::default()
+    }))
+    .add_plugins(GamePlugin)
+    .run();
 }
@@ -1,4 +1,7 @@
-use crate::{persistence::add_load_button, prelude::*};
+use crate::{
+    persistence::{add_load_button, Settings},
+    prelude::*,
+};
 
 pub struct MenuPlugin;
 
@@ -7,7 +10,11 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Menu), setup_menu)
-            .add_systems(Update, click_play_button.run_if(in_state(GameState::Menu)))
+            .add_systems(
+                Update,
+                (click_play_button, click_language_button, show_language)
+                    .run_if(in_state(GameState::Menu)),
+            )
             .add_systems(OnExit(GameState::Menu), hide_menu);
     }
 }
@@ -56,6 +63,30 @@ fn setup_menu(
             Menu,
         ))
         .with_children(|parent| {
+            let button_colors = ButtonColors::default();
+            parent
+                .spawn((
+                    ButtonBundle {
+                        background_color: button_colors.normal.into(),
+                        ..Default::default()
+                    },
+                    button_colors,
+                    Class::new("main_menu__language"),
+                    LanguageButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 24.0,
+                                color: Color::rgb(0.9, 0.9, 0.9),
+                                ..default()
+                            },
+                        ),
+                        LanguageText,
+                    ));
+                });
             add_load_button(parent);
             let button_colors = ButtonColors::default();
             parent
@@ -76,13 +107,16 @@ fn setup_menu(
                     ChangeState(GameState::Looting),
                 ))
                 .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Play",
-                        TextStyle {
-                            font_size: 40.0,
-                            color: Color::rgb(0.9, 0.9, 0.9),
-                            ..default()
-                        },
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 40.0,
+                                color: Color::rgb(0.9, 0.9, 0.9),
+                                ..default()
+                            },
+                        ),
+                        LocalizedText("menu.play"),
                     ));
                 });
             parent.spawn((
@@ -102,6 +136,13 @@ struct ChangeState(GameState);
 #[derive(Component)]
 struct OpenLink(&'static str);
 
+// Cycles through the locales, remembering the choice in the settings file.
+#[derive(Component)]
+struct LanguageButton;
+
+#[derive(Component)]
+struct LanguageText;
+
 fn click_play_button(
     mut next_state: ResMut<NextState<GameState>>,
     mut interaction_query: Query<
@@ -139,6 +180,42 @@ fn click_play_button(
     }
 }
 
+fn click_language_button(
+    mut localization: ResMut<Localization>,
+    mut settings: ResMut<Settings>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<LanguageButton>)>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let languages = localization.languages();
+        let next = languages
+            .iter()
+            .position(|language| *language == localization.language())
+            .map_or(0, |index| (index + 1) % languages.len());
+        if let Some(language) = languages.get(next).map(|language| language.to_string()) {
+            localization.set_language(&language);
+            settings.language = language;
+            settings.save();
+        }
+    }
+}
+
+fn show_language(
+    localization: Res<Localization>,
+    mut texts: Query<(&mut Text, Ref<LanguageText>)>,
+) {
+    for (mut text, marker) in texts.iter_mut() {
+        if localization.is_changed() || marker.is_added() {
+            let name = localization
+                .locale()
+                .map_or(localization.language(), |locale| locale.name.as_str());
+            text.sections[0].value = localization.format("menu.language", &[("language", &name)]);
+        }
+    }
+}
+
 fn hide_menu(mut commands: Commands, query: Query<Entity, With<Menu>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
@@ -0,0 +1,317 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    loading::{ContentEntry, ContentManifest, CONTENT_FILES, CONTENT_FOLDERS},
+    locale::Locale,
+    prelude::*,
+    summoner::{Fusion, Synergies},
+};
+
+// Packs sit beside assets/, one folder each, and are read through the "mods" asset source.
+pub const MODS_FOLDER: &str = "mods";
+pub const MODS_SOURCE: &str = "mods";
+pub const PACK_MANIFEST: &str = "pack.ron";
+
+// mods/<pack>/pack.ron. Packs load in ascending load_order, then by name, and later packs win.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PackManifest {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub load_order: i32,
+}
+
+pub struct ModPack {
+    pub folder: String,
+    pub manifest: PackManifest,
+}
+
+// What rune codes record about a pack, so an army only loads where the same content exists.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PackId {
+    pub name: String,
+    pub version: String,
+}
+
+impl fmt::Display for PackId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.version)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ActivePacks {
+    // In load order.
+    pub packs: Vec<PackId>,
+    // Files more than one pack replaces; the last pack's copy is the one loaded.
+    pub conflicts: Vec<String>,
+    // Base game files a pack replaces.
+    pub overrides: Vec<String>,
+    // Packs left out because they could not be read, with the reason.
+    pub skipped: Vec<String>,
+}
+
+pub fn mods_root(assets_root: &Path) -> PathBuf {
+    assets_root.with_file_name(MODS_FOLDER)
+}
+
+// Where a manifest path lives on disk, for manifests merged with packs.
+pub fn content_file(assets_root: &Path, path: &str) -> PathBuf {
+    match path.strip_prefix(&format!("{}://", MODS_SOURCE)) {
+        Some(path) => mods_root(assets_root).join(path),
+        None => assets_root.join(path),
+    }
+}
+
+// Every folder in mods/ with a pack manifest, in load order, and an error for each pack
+// whose manifest can't be read. No mods/ folder means no packs.
+pub fn discover_packs(mods_root: &Path) -> (Vec<ModPack>, Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(mods_root) else {
+        return (vec![], vec![]);
+    };
+    let mut packs = vec![];
+    let mut errors = vec![];
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => {
+                errors.push(err.to_string());
+                continue;
+            }
+        };
+        let manifest = path.join(PACK_MANIFEST);
+        if !manifest.is_file() {
+            continue;
+        }
+        let folder = path.file_name().unwrap().to_string_lossy().to_string();
+        let manifest = std::fs::read_to_string(&manifest)
+            .map_err(|err| err.to_string())
+            .and_then(|source| ron::from_str(&source).map_err(|err| err.to_string()));
+        match manifest {
+            Ok(manifest) => packs.push(ModPack { folder, manifest }),
+            Err(err) => errors.push(format!("{}/{}: {}", folder, PACK_MANIFEST, err)),
+        }
+    }
+    packs.sort_by(|a, b| {
+        (a.manifest.load_order, &a.manifest.name).cmp(&(b.manifest.load_order, &b.manifest.name))
+    });
+    (packs, errors)
+}
+
+// Parses a pack's content file as what its manifest key loads, so a broken file skips the
+// pack rather than stalling the Loading state. Sounds are only played, so they aren't parsed.
+fn check_pack_file(key: &str, path: &Path) -> Result<(), String> {
+    fn parse<T: for<'de> Deserialize<'de>>(source: &str) -> Result<(), String> {
+        ron::from_str::<T>(source)
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+    if key == "sounds" {
+        return Ok(());
+    }
+    let source = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    match key {
+        "player_summons" | "npc_summons" | "fused_summons" => parse::<SummonType>(&source),
+        "fusions" => parse::<Fusion>(&source),
+        "waves" => parse::<SummonedMinions>(&source),
+        "brains" => parse::<CharacterBrainDef>(&source),
+        "locales" => parse::<Locale>(&source),
+        "story" => parse::<Story>(&source),
+        "synergies" => parse::<Synergies>(&source),
+        _ => Ok(()),
+    }
+}
+
+// The manifest key and path of every file the pack adds, each one checked.
+fn pack_files(mods_root: &Path, pack: &ModPack) -> Result<Vec<(&'static str, String)>, String> {
+    let pack_root = mods_root.join(&pack.folder);
+    let mut pack_files = vec![];
+    let mut add = |key: &'static str, path: PathBuf| {
+        let relative = path.strip_prefix(&pack_root).unwrap().to_string_lossy();
+        let relative = relative.replace('\\', "/");
+        check_pack_file(key, &path).map_err(|err| format!("{}: {}", relative, err))?;
+        pack_files.push((
+            key,
+            format!("{}://{}/{}", MODS_SOURCE, pack.folder, relative),
+        ));
+        Ok::<_, String>(())
+    };
+    for (key, folder, extension) in CONTENT_FOLDERS {
+        let Ok(entries) = std::fs::read_dir(pack_root.join(folder)) else {
+            continue;
+        };
+        let mut files = vec![];
+        for entry in entries {
+            let path = entry.map_err(|err| err.to_string())?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some(extension) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        for file in files {
+            add(key, file)?;
+        }
+    }
+    for (key, file) in CONTENT_FILES {
+        if pack_root.join(file).is_file() {
+            add(key, pack_root.join(file))?;
+        }
+    }
+    Ok(pack_files)
+}
+
+// Adds each pack's content to the manifest. A pack file replaces the entry with the same
+// file stem, since that is what the game looks content up by. A pack with a file that can't
+// be read is skipped whole, so the rest still load.
+pub fn apply_packs(
+    manifest: &mut ContentManifest,
+    mods_root: &Path,
+    packs: &[ModPack],
+) -> Result<ActivePacks, String> {
+    let mut active = ActivePacks::default();
+    let pack_prefix = format!("{}://", MODS_SOURCE);
+    for pack in packs {
+        let files = match pack_files(mods_root, pack) {
+            Ok(files) => files,
+            Err(err) => {
+                active.skipped.push(format!("{}/{}", pack.folder, err));
+                continue;
+            }
+        };
+        for (key, new) in files {
+            let old = match manifest.0.get_mut(key) {
+                Some(ContentEntry::File { path }) => path,
+                Some(ContentEntry::Files { paths }) => {
+                    let stem = Path::new(&new).file_stem();
+                    match paths
+                        .iter()
+                        .position(|path| Path::new(path.as_str()).file_stem() == stem)
+                    {
+                        Some(index) => &mut paths[index],
+                        None => {
+                            paths.push(new);
+                            continue;
+                        }
+                    }
+                }
+                None => return Err(format!("content manifest is missing \"{}\"", key)),
+            };
+            if old.starts_with(&pack_prefix) {
+                active.conflicts.push(format!("{} replaces {}", new, old));
+            } else {
+                active.overrides.push(format!("{} replaces {}", new, old));
+            }
+            *old = new;
+        }
+        active.packs.push(PackId {
+            name: pack.manifest.name.clone(),
+            version: pack.manifest.version.clone(),
+        });
+    }
+    Ok(active)
+}
+
+// The content manifest with every readable pack in mods/ applied. Only a broken base manifest
+// is an error.
+pub fn read_content(assets_root: &Path) -> Result<(ContentManifest, ActivePacks), String> {
+    read_content_with(assets_root, &mods_root(assets_root))
+}
+
+fn read_content_with(
+    assets_root: &Path,
+    mods_root: &Path,
+) -> Result<(ContentManifest, ActivePacks), String> {
+    let mut manifest = ContentManifest::read(assets_root)?;
+    let (packs, errors) = discover_packs(mods_root);
+    let mut active = apply_packs(&mut manifest, mods_root, &packs)?;
+    active.skipped.splice(0..0, errors);
+    Ok((manifest, active))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: PathBuf, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn packs_override_by_stem_in_load_order() {
+        let mods_root = std::env::temp_dir().join(format!("mods_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&mods_root);
+        let brain = "Sequence([])";
+        write(
+            mods_root.join("early/pack.ron"),
+            "(name: \"Early\", version: \"1.0\", load_order: -1)",
+        );
+        write(mods_root.join("early/brains/fighter.brain"), brain);
+        write(mods_root.join("early/brains/lurker.brain"), brain);
+        write(
+            mods_root.join("late/pack.ron"),
+            "(name: \"Late\", version: \"0.2\")",
+        );
+        write(mods_root.join("late/brains/fighter.brain"), brain);
+        write(
+            mods_root.join("late/story.teller"),
+            "(waves: [], winning_beats: [], losing_beats: [], agnostic_beats: [])",
+        );
+        write(mods_root.join("late/tribes.synergies"), "([])");
+        write(mods_root.join("no_manifest/brains/bane.brain"), brain);
+        write(mods_root.join("broken/pack.ron"), "(name: \"Broken\"");
+        write(
+            mods_root.join("typo/pack.ron"),
+            "(name: \"Typo\", version: \"1.0\")",
+        );
+        write(mods_root.join("typo/brains/lurker.brain"), "Sequence([");
+
+        let (mut manifest, active) = read_content_with(Path::new("assets"), &mods_root).unwrap();
+        std::fs::remove_dir_all(&mods_root).unwrap();
+
+        assert_eq!(
+            active.packs,
+            vec![
+                PackId {
+                    name: "Early".to_string(),
+                    version: "1.0".to_string()
+                },
+                PackId {
+                    name: "Late".to_string(),
+                    version: "0.2".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            active.conflicts,
+            vec!["mods://late/brains/fighter.brain replaces mods://early/brains/fighter.brain"]
+        );
+        assert_eq!(
+            active.overrides,
+            vec![
+                "mods://early/brains/fighter.brain replaces brains/fighter.brain",
+                "mods://late/story.teller replaces story.teller",
+                "mods://late/tribes.synergies replaces tribes.synergies"
+            ]
+        );
+        // Broken packs are left out, and the others still load.
+        assert_eq!(active.skipped.len(), 2, "{:?}", active.skipped);
+        assert!(active.skipped[0].starts_with("broken/pack.ron: "));
+        assert!(active.skipped[1].starts_with("typo/brains/lurker.brain: "));
+        let Some(ContentEntry::Files { paths }) = manifest.0.get_mut("brains") else {
+            panic!("brains should be listed");
+        };
+        assert!(paths.contains(&"mods://late/brains/fighter.brain".to_string()));
+        assert!(paths.contains(&"mods://early/brains/lurker.brain".to_string()));
+        assert!(!paths
+            .iter()
+            .any(|path| path.contains("bane") || path.contains("typo")));
+        assert!(matches!(
+            manifest.0.get("story"),
+            Some(ContentEntry::File { path }) if path == "mods://late/story.teller"
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod runes;
 pub use runes::*;
+pub mod settings;
+pub use settings::*;
 pub mod ui;
 pub use ui::*;
 
@@ -9,7 +11,8 @@ pub struct PersistencePlugin;
 
 impl Plugin for PersistencePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SaveData>();
+        app.init_resource::<SaveData>()
+            .insert_resource(Settings::load());
 
         app.add_systems(Update, (save_on_click).run_if(in_state(GameState::Victory)));
 
@@ -0,0 +1,50 @@
+use crate::{locale::ENGLISH, prelude::*};
+
+// Kept beside the game, rather than in the save, since it isn't part of a run.
+#[cfg(not(target_arch = "wasm32"))]
+const SETTINGS_FILE: &str = "settings.ron";
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct Settings {
+    pub language: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: ENGLISH.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    // Defaults when there is no settings file yet, or it can't be read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Self {
+        std::fs::read_to_string(SETTINGS_FILE)
+            .ok()
+            .and_then(|source| ron::from_str(&source).ok())
+            .unwrap_or_default()
+    }
+
+    // The web build has nowhere to keep settings between visits.
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) {
+        let result = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|err| err.to_string())
+            .and_then(|source| {
+                std::fs::write(SETTINGS_FILE, source).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!("Could not save {}: {}", SETTINGS_FILE, err);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self) {}
+}
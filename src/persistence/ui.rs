@@ -1,4 +1,8 @@
-use crate::prelude::*;
+use crate::{
+    mods::{ActivePacks, PackId},
+    prelude::*,
+    summoner::UnrankedMinions,
+};
 
 #[derive(Component)]
 pub struct ShareArmyButton;
@@ -16,17 +20,20 @@ pub fn add_save_button(parent: &mut ChildBuilder) {
             Class::new("share_army"),
         ))
         .with_children(|parent| {
-            parent.spawn((TextBundle {
-                text: Text::from_section(
-                    "Share Army with Friends".to_string(),
-                    TextStyle {
-                        font: Default::default(),
-                        font_size: 32.,
-                        color: Color::BLACK,
-                    },
-                ),
-                ..default()
-            },));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: Default::default(),
+                            font_size: 32.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..default()
+                },
+                LocalizedText("save.share_army"),
+            ));
         });
 }
 #[cfg(not(target_arch = "wasm32"))]
@@ -39,17 +46,20 @@ pub fn add_save_button(parent: &mut ChildBuilder) {
             Class::new("share_army"),
         ))
         .with_children(|parent| {
-            parent.spawn((TextBundle {
-                text: Text::from_section(
-                    "Copy Army to Clipboard".to_string(),
-                    TextStyle {
-                        font: Default::default(),
-                        font_size: 32.,
-                        color: Color::BLACK,
-                    },
-                ),
-                ..default()
-            },));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: Default::default(),
+                            font_size: 32.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..default()
+                },
+                LocalizedText("save.copy_army"),
+            ));
         });
 }
 
@@ -63,28 +73,38 @@ pub fn add_load_button(parent: &mut ChildBuilder) {
             LoadArmyButton,
         ))
         .with_children(|parent| {
-            parent.spawn((TextBundle {
-                text: Text::from_section(
-                    "Battle Another Summoner".to_string(),
-                    TextStyle {
-                        font: Default::default(),
-                        font_size: 32.,
-                        color: Color::BLACK,
-                    },
-                ),
-                ..default()
-            },));
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: Default::default(),
+                            font_size: 32.,
+                            color: Color::BLACK,
+                        },
+                    ),
+                    ..default()
+                },
+                LocalizedText("save.battle_summoner"),
+            ));
         });
 }
 
 pub fn save_on_click(
     save_data: Option<Res<SaveData>>,
+    active_packs: Res<ActivePacks>,
     interactions: Query<&Interaction, (Changed<Interaction>, With<ShareArmyButton>)>,
 ) {
     for interaction in interactions.iter() {
         if *interaction == Interaction::Pressed && save_data.is_some() {
             let data = save_data.as_ref().unwrap();
-            store_in_runes(&data.armies, true);
+            store_in_runes(
+                SaveData {
+                    armies: data.armies.clone(),
+                    packs: active_packs.packs.clone(),
+                },
+                true,
+            );
             #[cfg(target_arch = "wasm32")]
             show_clipboard("2em", "50%");
         }
@@ -98,11 +118,12 @@ pub fn load_on_click(
     mut wave_assets: ResMut<Assets<SummonedMinions>>,
     mut summon_assets: ResMut<SummonsAssets>,
     mut story: ResMut<Story>,
+    active_packs: Res<ActivePacks>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     for interaction in interactions.iter() {
         if *interaction == Interaction::Pressed {
-            match retrieve_from_runes::<SaveData>() {
+            match retrieve_save(&active_packs) {
                 Ok(save) => {
                     *story = Story::from_save_data(
                         &save,
@@ -159,9 +180,10 @@ pub fn load_on_event(
     mut wave_assets: ResMut<Assets<SummonedMinions>>,
     mut summon_assets: ResMut<SummonsAssets>,
     mut story: ResMut<Story>,
+    active_packs: Res<ActivePacks>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    match retrieve_from_runes::<SaveData>() {
+    match retrieve_save(&active_packs) {
         Ok(save) => {
             *story =
                 Story::from_save_data(&save, &summon_types, &mut wave_assets, &mut summon_assets);
@@ -176,4 +198,43 @@ pub fn load_on_event(
 #[derive(Resource, Serialize, Deserialize, Default)]
 pub struct SaveData {
     pub armies: Vec<SummonedMinions>,
+    // The mod packs the armies were built with, in load order.
+    pub packs: Vec<PackId>,
+}
+
+impl SaveData {
+    // Armies refer to summons by short code, so they only mean the same thing with the same packs.
+    pub fn check_packs(&self, active: &ActivePacks) -> Result<(), String> {
+        if self.packs == active.packs {
+            return Ok(());
+        }
+        let list = |packs: &[PackId]| match packs.len() {
+            0 => "no mod packs".to_string(),
+            _ => packs
+                .iter()
+                .map(|pack| pack.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        Err(format!(
+            "army needs {}, but {} are active",
+            list(&self.packs),
+            list(&active.packs)
+        ))
+    }
+}
+
+// Runes copied before mod packs existed hold only the armies, and no packs were in play.
+#[derive(Deserialize)]
+struct ArmiesOnly(Vec<UnrankedMinions>);
+
+fn retrieve_save(active: &ActivePacks) -> Result<SaveData, String> {
+    let save = retrieve_from_runes::<SaveData>().or_else(|_| {
+        retrieve_from_runes::<ArmiesOnly>().map(|ArmiesOnly(armies)| SaveData {
+            armies: armies.into_iter().map(SummonedMinions::from).collect(),
+            packs: vec![],
+        })
+    })?;
+    save.check_packs(active)?;
+    Ok(save)
 }
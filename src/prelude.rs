@@ -1,23 +1,24 @@
 pub use crate::battle::{
-    Action, Attack, AuraEffect, BehaviorController, BehaviorModel, CharacterBrain,
+    Action, Attack, AuraEffect, BehaviorController, BehaviorModel, Blackboard, CharacterBrain,
     CharacterBrainDef, CharacterStats, Faction, Movement,
 };
 #[cfg(target_arch = "wasm32")]
 pub use crate::bindings::*;
 pub use crate::board::BoardMouseState;
-pub use crate::bt::*;
 pub use crate::flow::*;
 pub use crate::loading::{AudioAssets, BrainAssets, StyleAssets, SummonsAssets, TextureAssets};
+pub use crate::locale::{Localization, LocalizedText};
 pub use crate::persistence::runes::*;
 pub use crate::state::GameState;
-pub use crate::summoner::{EnemyMinions, KnownSummons, Mana, SummonedMinions};
-pub use crate::summons::{spawn_summon, Summon, SummonType};
+pub use crate::summoner::{EnemyMinions, KnownSummons, Mana, SummonedMinions, Veterans};
+pub use crate::summons::{spawn_summon, SoundCue, Summon, SummonType};
 pub use bevy::prelude::*;
 pub use bevy::utils::HashMap;
 pub use bevy_asset_loader::prelude::*;
 pub use bevy_ecss::prelude::*;
 pub use rand::prelude::*;
 pub use serde::{Deserialize, Serialize};
+pub use unpowered::*;
 
 pub use std::f32::consts::PI;
 pub const WINDOW_SIZE: (f32, f32) = (948., 533.);
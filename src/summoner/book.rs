@@ -2,7 +2,7 @@ use bevy::transform::commands;
 
 use crate::prelude::*;
 
-use super::spawn_mana_bar;
+use super::{control_held, spawn_formation_text, spawn_mana_bar, spawn_synergy_text};
 
 #[derive(Resource, Default)]
 pub struct KnownSummons {
@@ -46,11 +46,39 @@ impl KnownSummons {
     pub fn add(&mut self, summon: SummonType) {
         let name = summon.name().to_string();
         self.summons.insert(name.clone(), summon);
-        if self.summons.len() <= HOTKEYS.len() {
-            self.hotkeys
-                .insert(HOTKEYS[self.summons.len() - 1], name.clone());
-            self.summon_hotkeys
-                .insert(name, HOTKEYS[self.summons.len() - 1]);
+        // Fusing can free a key in the middle of the row.
+        if let Some(key) = HOTKEYS.iter().find(|key| !self.hotkeys.contains_key(*key)) {
+            self.hotkeys.insert(*key, name.clone());
+            self.summon_hotkeys.insert(name, *key);
+        }
+    }
+
+    // Swaps both inputs for the output. The output takes over the first input's hotkey, and
+    // the second's goes to a summon that had none.
+    pub fn fuse(&mut self, inputs: (&String, &String), output: SummonType) {
+        let name = output.name().to_string();
+        let mut freed = vec![];
+        for input in [inputs.0, inputs.1] {
+            self.summons.remove(input);
+            if let Some(key) = self.summon_hotkeys.remove(input) {
+                self.hotkeys.remove(&key);
+                freed.push(key);
+            }
+            if self.active.as_ref() == Some(input) {
+                self.active = None;
+            }
+        }
+        self.summons.insert(name.clone(), output);
+        let mut unbound: Vec<_> = self
+            .summons
+            .keys()
+            .filter(|summon| !self.summon_hotkeys.contains_key(*summon) && **summon != name)
+            .cloned()
+            .collect();
+        unbound.sort();
+        for (key, summon) in freed.into_iter().zip(std::iter::once(name).chain(unbound)) {
+            self.hotkeys.insert(key, summon.clone());
+            self.summon_hotkeys.insert(summon, key);
         }
     }
 
@@ -104,6 +132,7 @@ pub fn spawn_summon_button(
     spawner: &mut ChildBuilder,
     styles: &StyleAssets,
     texture_assets: &TextureAssets,
+    localization: &Localization,
     summon: &SummonType,
 ) -> Entity {
     spawner
@@ -126,7 +155,7 @@ pub fn spawn_summon_button(
             parent.spawn((
                 TextBundle {
                     text: Text::from_sections(vec![TextSection {
-                        value: summon.name().to_string(),
+                        value: summon.display_name(localization),
                         style: TextStyle {
                             font: Default::default(),
                             font_size: 20.0,
@@ -169,7 +198,7 @@ pub fn spawn_summon_button(
             parent.spawn((
                 TextBundle {
                     text: Text::from_sections(vec![TextSection {
-                        value: format!("Mana: {}", summon.mana_cost()),
+                        value: localization.format("summon.mana", &[("mana", &summon.mana_cost())]),
                         style: TextStyle {
                             font: Default::default(),
                             font_size: 20.0,
@@ -219,6 +248,7 @@ pub fn spawn_summoning_scroll(
     mut commands: Commands,
     styles: Res<StyleAssets>,
     texture_assets: Res<TextureAssets>,
+    localization: Res<Localization>,
     known_summons: Res<KnownSummons>,
 ) {
     commands
@@ -256,9 +286,17 @@ pub fn spawn_summoning_scroll(
                         .spawn((NodeBundle::default(), Class::new("summon_scroll__summons")))
                         .with_children(|parent| {
                             for summon in known_summons.summons.values() {
-                                spawn_summon_button(parent, &styles, &texture_assets, summon);
+                                spawn_summon_button(
+                                    parent,
+                                    &styles,
+                                    &texture_assets,
+                                    &localization,
+                                    summon,
+                                );
                             }
                         });
+                    spawn_synergy_text(parent);
+                    spawn_formation_text(parent);
                     spawn_mana_bar(parent, &styles);
                 });
         });
@@ -341,7 +379,7 @@ pub fn handle_summon_button_interactions(
             }
         }
         if let Some(key) = known_summons.get_hotkey(&button.1.name().to_string()) {
-            if key_input.just_pressed(key) {
+            if key_input.just_pressed(key) && !control_held(&key_input) {
                 known_summons.active = Some(button.1.name().to_string());
             }
         }
@@ -0,0 +1,411 @@
+use bevy::window::ReceivedCharacter;
+
+use crate::{board::PLAYER_ROWS, prelude::*};
+
+use super::{control_held, show_placement_edit, PlacementEdit, PlacementHistory};
+
+// Presets live beside the game like the settings, so they carry over between runs.
+#[cfg(not(target_arch = "wasm32"))]
+const FORMATIONS_FILE: &str = "formations.ron";
+
+// Applies the preset in the same slot. With Shift held, names the board and stores it there
+// instead.
+pub const PRESET_KEYS: [KeyCode; 4] = [KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4];
+const MAX_NAME_LENGTH: usize = 24;
+
+// Where the player put which summons, by summon name.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Formation {
+    pub name: String,
+    // Sorted by tile, which is also the order units are kept in when mana runs short.
+    pub units: Vec<((usize, usize), String)>,
+}
+
+impl Formation {
+    pub fn of(name: &str, minions: &SummonedMinions) -> Self {
+        let mut units: Vec<_> = minions
+            .iter()
+            .map(|(tile, summon)| (*tile, summon.clone()))
+            .collect();
+        units.sort();
+        Self {
+            name: name.to_string(),
+            units,
+        }
+    }
+
+    // The edit that swaps the board for this formation. Units the player no longer knows,
+    // that sit off the player's rows, or that don't fit in `max_mana` are left out.
+    pub fn deploy(
+        &self,
+        minions: &SummonedMinions,
+        known_summons: &KnownSummons,
+        max_mana: i32,
+    ) -> PlacementEdit {
+        let (mut placed, kept): (Vec<_>, Vec<_>) = minions
+            .iter()
+            .map(|(tile, summon)| (*tile, summon.clone()))
+            .partition(|(_, summon)| known_summons.has(summon));
+        placed.sort();
+        // Summons the player no longer knows can't be taken off without their type, so they
+        // stay, holding their tiles and the mana they cost.
+        let mut mana = minions.mana();
+        let mut edits = vec![];
+        for (tile, summon) in placed {
+            let summon = known_summons.get(&summon);
+            mana -= summon.mana_cost();
+            edits.push(PlacementEdit::Remove(summon, tile));
+        }
+        for ((x, y), summon) in &self.units {
+            if !known_summons.has(summon)
+                || *x >= BOARD_SIZE as usize
+                || *y >= PLAYER_ROWS
+                || kept.iter().any(|(tile, _)| *tile == (*x, *y))
+            {
+                continue;
+            }
+            let summon = known_summons.get(summon);
+            if mana + summon.mana_cost() > max_mana {
+                continue;
+            }
+            mana += summon.mana_cost();
+            edits.push(PlacementEdit::Add(summon, (*x, *y)));
+        }
+        PlacementEdit::Batch(edits)
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Formations {
+    // The army that fought last, put back when the next Summoning starts.
+    #[serde(skip)]
+    pub last: Formation,
+    // By slot in PRESET_KEYS.
+    #[serde(default)]
+    pub presets: HashMap<usize, Formation>,
+}
+
+impl Formations {
+    // No presets when there is no formations file yet, or it can't be read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Self {
+        std::fs::read_to_string(FORMATIONS_FILE)
+            .ok()
+            .and_then(|source| ron::from_str(&source).ok())
+            .unwrap_or_default()
+    }
+
+    // The web build has nowhere to keep presets between visits.
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) {
+        let result = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|err| err.to_string())
+            .and_then(|source| {
+                std::fs::write(FORMATIONS_FILE, source).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!("Could not save {}: {}", FORMATIONS_FILE, err);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self) {}
+}
+
+#[derive(Resource, Default)]
+pub struct PresetNaming {
+    // The slot the board is being stored in, and the name typed so far.
+    pub editing: Option<(usize, String)>,
+    // The slot last stored or applied this Summoning, whose name is shown.
+    pub shown: Option<usize>,
+}
+
+// While a name is typed, keys that would otherwise place, remove or start the battle are
+// left alone.
+pub fn naming_preset(naming: Res<PresetNaming>) -> bool {
+    naming.editing.is_some()
+}
+
+pub fn forget_preset_name(mut naming: ResMut<PresetNaming>) {
+    *naming = PresetNaming::default();
+}
+
+pub fn remember_formation(minions: Res<SummonedMinions>, mut formations: ResMut<Formations>) {
+    formations.last = Formation::of("Last battle", &minions);
+}
+
+// Not undoable, since the board it replaces is the empty one Summoning starts with.
+pub fn restore_last_formation(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    sounds: Res<AudioAssets>,
+    formations: Res<Formations>,
+    known_summons: Res<KnownSummons>,
+    mana: Res<Mana>,
+    mut summoned: ResMut<SummonedMinions>,
+    mut previews: Query<(Entity, &mut Summon)>,
+) {
+    let edit = formations
+        .last
+        .deploy(&summoned, &known_summons, mana.max_mana);
+    if edit != PlacementEdit::Batch(vec![]) && edit.apply(&mut summoned) {
+        show_placement_edit(&edit, &mut commands, &textures, &sounds, &mut previews);
+    }
+}
+
+pub fn use_formation_presets(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    textures: Res<TextureAssets>,
+    sounds: Res<AudioAssets>,
+    formations: Res<Formations>,
+    mut naming: ResMut<PresetNaming>,
+    known_summons: Res<KnownSummons>,
+    mana: Res<Mana>,
+    mut history: ResMut<PlacementHistory>,
+    mut summoned: ResMut<SummonedMinions>,
+    mut previews: Query<(Entity, &mut Summon)>,
+) {
+    let Some(slot) = PRESET_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+    else {
+        return;
+    };
+    if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        // Overwriting starts from the preset's old name.
+        let name = match formations.presets.get(&slot) {
+            Some(preset) => preset.name.clone(),
+            None => preset_name(slot, ""),
+        };
+        naming.editing = Some((slot, name));
+        return;
+    }
+    if control_held(&keyboard_input) {
+        return;
+    }
+    let edit = formations
+        .presets
+        .get(&slot)
+        .map(|preset| preset.deploy(&summoned, &known_summons, mana.max_mana));
+    match edit {
+        Some(edit) if history.edit(edit.clone(), &mut summoned) => {
+            show_placement_edit(&edit, &mut commands, &textures, &sounds, &mut previews);
+            naming.shown = Some(slot);
+        }
+        _ => {
+            commands.spawn(AudioBundle {
+                source: sounds.error.clone(),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+// Typing edits the name, Enter stores the board under it and Escape backs out. The keys
+// are cleared so the rest of Summoning doesn't see them this frame.
+pub fn name_formation_preset(
+    mut commands: Commands,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    sounds: Res<AudioAssets>,
+    mut naming: ResMut<PresetNaming>,
+    mut formations: ResMut<Formations>,
+    summoned: Res<SummonedMinions>,
+) {
+    let Some((slot, name)) = naming.editing.as_mut() else {
+        characters.clear();
+        return;
+    };
+    for character in characters.read() {
+        for typed in character.char.chars().filter(|typed| !typed.is_control()) {
+            if name.chars().count() < MAX_NAME_LENGTH {
+                name.push(typed);
+            }
+        }
+    }
+    if keyboard_input.clear_just_pressed(KeyCode::Backspace) {
+        name.pop();
+    }
+    if keyboard_input.clear_just_pressed(KeyCode::Escape) {
+        naming.editing = None;
+    } else if keyboard_input.clear_just_pressed(KeyCode::Enter) {
+        let slot = *slot;
+        let name = preset_name(slot, name);
+        formations
+            .presets
+            .insert(slot, Formation::of(&name, &summoned));
+        formations.save();
+        commands.spawn(AudioBundle {
+            source: sounds.place.clone(),
+            ..Default::default()
+        });
+        naming.editing = None;
+        naming.shown = Some(slot);
+    }
+}
+
+// A blank name falls back to the slot's default.
+fn preset_name(slot: usize, typed: &str) -> String {
+    match typed.trim() {
+        "" => format!("Formation {}", slot + 1),
+        name => name.to_string(),
+    }
+}
+
+#[derive(Component)]
+pub struct FormationText;
+
+pub fn spawn_formation_text(parent: &mut ChildBuilder) -> Entity {
+    parent
+        .spawn((
+            TextBundle {
+                text: Text::from_sections(vec![TextSection {
+                    value: "".to_string(),
+                    style: TextStyle {
+                        font: Default::default(),
+                        font_size: 14.0,
+                        color: Color::WHITE,
+                    },
+                }]),
+                ..Default::default()
+            },
+            Class::new("summon_scroll__formation"),
+            FormationText,
+        ))
+        .id()
+}
+
+pub fn show_formation_name(
+    naming: Res<PresetNaming>,
+    formations: Res<Formations>,
+    localization: Res<Localization>,
+    mut text_query: Query<&mut Text, With<FormationText>>,
+) {
+    let key = |slot: usize| format!("F{}", slot + 1);
+    let line = match (&naming.editing, naming.shown) {
+        (Some((slot, name)), _) => {
+            localization.format("formation.naming", &[("key", &key(*slot)), ("name", name)])
+        }
+        (None, Some(slot)) => match formations.presets.get(&slot) {
+            Some(preset) => localization.format(
+                "formation.preset",
+                &[("key", &key(slot)), ("name", &preset.name)],
+            ),
+            None => String::new(),
+        },
+        (None, None) => String::new(),
+    };
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != line {
+            text.sections[0].value = line.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deploying_drops_what_no_longer_fits() {
+        let summon = |name: &str, mana_cost: i32| -> SummonType {
+            ron::from_str(&format!(
+                r#"SummonType (
+                    summon_name: "{0}",
+                    sprite_idx: 0,
+                    mana_cost: {1},
+                    health: 1,
+                    stamina: 1,
+                    stamina_regen: 1,
+                    attacks: [],
+                    movements: [],
+                    brain: "fighter",
+                    short_code: "{0}",
+                    prerequisites: (0, None),
+                )"#,
+                name, mana_cost
+            ))
+            .unwrap()
+        };
+        let (skeleton, ghoul) = (summon("Skeleton", 1), summon("Ghoul", 2));
+        let mut fought = SummonedMinions::default();
+        fought.add_summon(skeleton.clone(), 0, 0);
+        fought.add_summon(ghoul.clone(), 1, 0);
+        fought.add_summon(skeleton.clone(), 2, 1);
+        fought.add_summon(summon("Pixie", 1), 3, 1);
+        let formation = Formation::of("Last battle", &fought);
+
+        // Pixie was fused away, and the second Skeleton no longer fits in the mana.
+        let known_summons = KnownSummons::new(vec![skeleton.clone(), ghoul.clone()]);
+        let max_mana = skeleton.mana_cost() + ghoul.mana_cost();
+        let mut minions = SummonedMinions::default();
+        let mut history = PlacementHistory::default();
+        let edit = formation.deploy(&minions, &known_summons, max_mana);
+        assert!(history.edit(edit, &mut minions));
+        assert_eq!(
+            Formation::of("Restored", &minions).units,
+            vec![
+                ((0, 0), "Skeleton".to_string()),
+                ((1, 0), "Ghoul".to_string())
+            ]
+        );
+        assert_eq!(minions.mana(), max_mana);
+
+        // Deploying over a board replaces it, and one undo brings it back.
+        minions.remove_summon(0, 0);
+        history.clear();
+        let other = Formation {
+            name: "Flank".to_string(),
+            units: vec![
+                ((7, 2), "Skeleton".to_string()),
+                ((7, 5), "Ghoul".to_string()),
+            ],
+        };
+        let edit = other.deploy(&minions, &known_summons, max_mana);
+        assert!(history.edit(edit, &mut minions));
+        assert_eq!(
+            Formation::of("", &minions).units,
+            vec![((7, 2), "Skeleton".to_string())]
+        );
+        history.undo(&mut minions).unwrap();
+        assert_eq!(
+            Formation::of("", &minions).units,
+            vec![((1, 0), "Ghoul".to_string())]
+        );
+        assert_eq!(minions.mana(), ghoul.mana_cost());
+
+        // A summon the player no longer knows stays put, and only the unit meant for its tile is
+        // dropped. Its mana still counts.
+        minions.add_summon(summon("Pixie", 1), 2, 0);
+        let mixed = Formation {
+            name: "Mixed".to_string(),
+            units: vec![
+                ((2, 0), "Skeleton".to_string()),
+                ((3, 0), "Skeleton".to_string()),
+                ((4, 0), "Ghoul".to_string()),
+            ],
+        };
+        let edit = mixed.deploy(&minions, &known_summons, max_mana);
+        assert!(history.edit(edit, &mut minions));
+        assert_eq!(
+            Formation::of("", &minions).units,
+            vec![
+                ((2, 0), "Pixie".to_string()),
+                ((3, 0), "Skeleton".to_string())
+            ]
+        );
+        assert_eq!(minions.mana(), max_mana - 1);
+    }
+
+    #[test]
+    fn blank_preset_names_fall_back_to_the_slot() {
+        assert_eq!(preset_name(1, "  Bone wall "), "Bone wall");
+        assert_eq!(preset_name(1, "   "), "Formation 2");
+    }
+}
@@ -0,0 +1,103 @@
+use std::borrow::Borrow;
+
+use crate::prelude::*;
+
+// A recipe from fusions/, merging two known summons into the output summon, which lives in
+// fused/ so it never turns up as ordinary loot. All three are summon names.
+#[derive(Serialize, Deserialize, Asset, TypePath, Clone, Debug)]
+pub struct Fusion {
+    pub inputs: (String, String),
+    pub output: String,
+}
+
+impl Fusion {
+    // Both inputs are known and the output isn't yet.
+    pub fn available(&self, known_summons: &KnownSummons) -> bool {
+        known_summons.has(&self.inputs.0)
+            && known_summons.has(&self.inputs.1)
+            && !known_summons.has(&self.output)
+    }
+
+    pub fn tagline(&self, localization: &Localization) -> String {
+        let name = |summon: &str| localization.content(&format!("summon.{}", summon), summon);
+        localization.format(
+            "loot.fuses",
+            &[
+                ("first", &name(&self.inputs.0)),
+                ("second", &name(&self.inputs.1)),
+            ],
+        )
+    }
+}
+
+// check-assets catches the same thing, but packs can bring recipes it never saw.
+pub fn validate_fusions_on_load(summons: Res<SummonsAssets>, fusions: Res<Assets<Fusion>>) {
+    for (name, handle) in summons.fusions.iter() {
+        let name: &str = name.borrow();
+        let Some(fusion) = fusions.get(handle) else {
+            continue;
+        };
+        if !summons.fused_summons.contains_key(fusion.output.as_str()) {
+            warn!(
+                "fusions/{}.fusion: output \"{}\" is not in fused/",
+                name, fusion.output
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fusing_replaces_both_inputs() {
+        let summon = |name: &str| -> SummonType {
+            ron::from_str(&format!(
+                r#"SummonType (
+                    summon_name: "{0}",
+                    sprite_idx: 0,
+                    mana_cost: 1,
+                    health: 1,
+                    stamina: 1,
+                    stamina_regen: 1,
+                    attacks: [],
+                    movements: [],
+                    brain: "fighter",
+                    short_code: "{0}",
+                    prerequisites: (0, None),
+                )"#,
+                name
+            ))
+            .unwrap()
+        };
+        let fusion = Fusion {
+            inputs: ("Skeleton".to_string(), "Ghoul".to_string()),
+            output: "Revenant".to_string(),
+        };
+        let mut known_summons =
+            KnownSummons::new(vec![summon("Skeleton"), summon("Pixie"), summon("Ghoul")]);
+        assert!(fusion.available(&known_summons));
+
+        known_summons.fuse((&fusion.inputs.0, &fusion.inputs.1), summon("Revenant"));
+        assert!(!fusion.available(&known_summons));
+        assert_eq!(known_summons.length(), 2);
+        assert!(!known_summons.has(&"Skeleton".to_string()));
+        assert!(!known_summons.has(&"Ghoul".to_string()));
+        assert_eq!(
+            known_summons.get_hotkey(&"Revenant".to_string()),
+            Some(KeyCode::KeyQ)
+        );
+
+        // The next summon takes the key Ghoul left free rather than clobbering one in use.
+        known_summons.add(summon("Wisp"));
+        assert_eq!(
+            known_summons.get_hotkey(&"Wisp".to_string()),
+            Some(KeyCode::KeyE)
+        );
+        assert_eq!(
+            known_summons.get_hotkey(&"Pixie".to_string()),
+            Some(KeyCode::KeyW)
+        );
+    }
+}
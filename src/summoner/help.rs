@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{battle::BrainSummaries, prelude::*};
 
 use super::SummonButton;
 
@@ -49,6 +49,35 @@ pub fn despawn_help_overlay(mut commands: Commands, query: Query<Entity, With<He
     }
 }
 
+pub fn hovered_unit<'a, T>(
+    board_mouse_state: &BoardMouseState,
+    mut units: impl Iterator<Item = (&'a Summon, T)>,
+) -> Option<T> {
+    let (x, y) = board_mouse_state.hovered_tile?;
+    units
+        .find(|(summon, _)| summon.x == x && summon.y == y)
+        .map(|(_summon, unit)| unit)
+}
+
+fn veteran_descriptor(
+    mut descriptor: Vec<TextSection>,
+    summon: &SummonType,
+    experience: i32,
+    localization: &Localization,
+) -> Vec<TextSection> {
+    if let Some(veterancy) = summon.veterancy(experience, localization) {
+        descriptor.push(TextSection {
+            value: format!("\n{}", veterancy),
+            style: TextStyle {
+                font: Default::default(),
+                font_size: 12.0,
+                color: Color::WHITE,
+            },
+        });
+    }
+    descriptor
+}
+
 pub fn show_hovered_stats(
     q_windows: Query<&Window, With<bevy::window::PrimaryWindow>>,
     board_mouse_state: Res<BoardMouseState>,
@@ -56,23 +85,46 @@ pub fn show_hovered_stats(
     mut overlay: Query<(&mut Visibility, &mut Style), With<HelpOverlay>>,
     mut text_query: Query<(&mut Text, &HelpOverlayText)>,
     stats_query: Query<(&Summon, &CharacterStats)>,
+    veterans: Res<Veterans>,
+    summaries: Res<BrainSummaries>,
+    localization: Res<Localization>,
 ) {
     let mouse_position = match q_windows.iter().next() {
         Some(window) => window.cursor_position(),
         None => return,
     };
-    let mut descriptor = None;
-    if let Some((x, y)) = board_mouse_state.hovered_tile {
-        if let Some((_summon, stats)) = stats_query
+    let mut descriptor = hovered_unit(
+        &board_mouse_state,
+        stats_query
             .iter()
-            .find(|(summon, _)| summon.x == x && summon.y == y)
-        {
-            descriptor = Some(stats.descriptor());
-        }
-    }
+            .map(|(summon, stats)| (summon, (summon, stats))),
+    )
+    .map(|(summon, stats)| {
+        let summon_type = &summon.summon_type;
+        veteran_descriptor(
+            summon_type.descriptor(
+                stats,
+                &summaries.describe(summon_type, &localization),
+                &localization,
+            ),
+            summon_type,
+            stats.experience,
+            &localization,
+        )
+    });
     for (button, interaction) in buttons.iter() {
         if *interaction == Interaction::Hovered {
-            descriptor = Some(button.1.descriptor());
+            let experience = veterans.experience(button.1.name());
+            descriptor = Some(veteran_descriptor(
+                button.1.descriptor(
+                    &button.1.veteran_stats(experience),
+                    &summaries.describe(&button.1, &localization),
+                    &localization,
+                ),
+                &button.1,
+                experience,
+                &localization,
+            ));
         }
     }
     if let Some(descriptor) = descriptor {
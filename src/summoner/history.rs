@@ -0,0 +1,257 @@
+use crate::prelude::*;
+
+use super::HeldSummon;
+
+// One change to the army during Summoning, kept so it can be taken back.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlacementEdit {
+    Add(SummonType, (usize, usize)),
+    Remove(SummonType, (usize, usize)),
+    Move((usize, usize), (usize, usize)),
+    // Several edits undone and redone together, in order.
+    Batch(Vec<PlacementEdit>),
+}
+
+impl PlacementEdit {
+    pub fn inverse(&self) -> Self {
+        match self {
+            PlacementEdit::Add(summon, tile) => PlacementEdit::Remove(summon.clone(), *tile),
+            PlacementEdit::Remove(summon, tile) => PlacementEdit::Add(summon.clone(), *tile),
+            PlacementEdit::Move(from, to) => PlacementEdit::Move(*to, *from),
+            PlacementEdit::Batch(edits) => {
+                PlacementEdit::Batch(edits.iter().rev().map(|edit| edit.inverse()).collect())
+            }
+        }
+    }
+
+    // False, leaving the army as it was, when the board doesn't allow the edit.
+    pub fn apply(&self, minions: &mut SummonedMinions) -> bool {
+        match self {
+            PlacementEdit::Add(summon, (x, y)) => {
+                if minions.has_spawn_location(*x, *y) {
+                    return false;
+                }
+                minions.add_summon(summon.clone(), *x, *y);
+                true
+            }
+            PlacementEdit::Remove(_, (x, y)) => minions.remove_summon(*x, *y),
+            PlacementEdit::Move(from, to) => minions.move_summon(*from, *to),
+            PlacementEdit::Batch(edits) => {
+                for (applied, edit) in edits.iter().enumerate() {
+                    if !edit.apply(minions) {
+                        // Take back the ones that went through, so nothing is half done.
+                        for edit in edits[..applied].iter().rev() {
+                            edit.inverse().apply(minions);
+                        }
+                        return false;
+                    }
+                }
+                true
+            }
+        }
+    }
+}
+
+// Undo and redo stacks over the edits made since entering Summoning. Making a new edit
+// drops whatever could have been redone.
+#[derive(Resource, Default)]
+pub struct PlacementHistory {
+    undo: Vec<PlacementEdit>,
+    redo: Vec<PlacementEdit>,
+}
+
+impl PlacementHistory {
+    // Applies the edit and remembers it, if the board allows it.
+    pub fn edit(&mut self, edit: PlacementEdit, minions: &mut SummonedMinions) -> bool {
+        if !edit.apply(minions) {
+            return false;
+        }
+        self.undo.push(edit);
+        self.redo.clear();
+        true
+    }
+
+    // The edit that was applied to take the last one back.
+    pub fn undo(&mut self, minions: &mut SummonedMinions) -> Option<PlacementEdit> {
+        let edit = self.undo.pop()?;
+        let inverse = edit.inverse();
+        if !inverse.apply(minions) {
+            // Something outside the history changed the board, so the rest no longer lines up.
+            self.clear();
+            return None;
+        }
+        self.redo.push(edit);
+        Some(inverse)
+    }
+
+    pub fn redo(&mut self, minions: &mut SummonedMinions) -> Option<PlacementEdit> {
+        let edit = self.redo.pop()?;
+        if !edit.apply(minions) {
+            self.clear();
+            return None;
+        }
+        self.undo.push(edit.clone());
+        Some(edit)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+// Ctrl turns Z and Y into undo and redo, so the summon hotkeys have to ignore them.
+pub fn control_held(keyboard_input: &ButtonInput<KeyCode>) -> bool {
+    keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+}
+
+// Mirrors an edit already applied to SummonedMinions onto the preview sprites.
+pub fn show_placement_edit(
+    edit: &PlacementEdit,
+    commands: &mut Commands,
+    textures: &Res<TextureAssets>,
+    sounds: &AudioAssets,
+    previews: &mut Query<(Entity, &mut Summon)>,
+) {
+    update_previews(edit, commands, textures, previews);
+    let sound = match edit {
+        PlacementEdit::Remove(..) => sounds.remove.clone(),
+        _ => sounds.place.clone(),
+    };
+    commands.spawn(AudioBundle {
+        source: sound,
+        ..Default::default()
+    });
+}
+
+fn update_previews(
+    edit: &PlacementEdit,
+    commands: &mut Commands,
+    textures: &Res<TextureAssets>,
+    previews: &mut Query<(Entity, &mut Summon)>,
+) {
+    match edit {
+        PlacementEdit::Add(summon, (x, y)) => {
+            spawn_summon(commands, textures, summon.clone(), *x, *y, false);
+        }
+        PlacementEdit::Remove(_, (x, y)) => {
+            for (entity, summon) in previews.iter() {
+                if summon.x == *x && summon.y == *y {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+        PlacementEdit::Move((x, y), to) => {
+            for (_, mut summon) in previews.iter_mut() {
+                if summon.x == *x && summon.y == *y {
+                    (summon.x, summon.y) = *to;
+                }
+            }
+        }
+        PlacementEdit::Batch(edits) => {
+            for edit in edits {
+                update_previews(edit, commands, textures, previews);
+            }
+        }
+    }
+}
+
+pub fn undo_placement(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    textures: Res<TextureAssets>,
+    sounds: Res<AudioAssets>,
+    mut history: ResMut<PlacementHistory>,
+    mut summoned: ResMut<SummonedMinions>,
+    mut held: ResMut<HeldSummon>,
+    mut previews: Query<(Entity, &mut Summon)>,
+) {
+    if !control_held(&keyboard_input) {
+        return;
+    }
+    let edit = if keyboard_input.just_pressed(KeyCode::KeyZ) {
+        history.undo(&mut summoned)
+    } else if keyboard_input.just_pressed(KeyCode::KeyY) {
+        history.redo(&mut summoned)
+    } else {
+        return;
+    };
+    // The held summon may be about to move or disappear.
+    held.0 = None;
+    match edit {
+        Some(edit) => show_placement_edit(&edit, &mut commands, &textures, &sounds, &mut previews),
+        None => {
+            commands.spawn(AudioBundle {
+                source: sounds.error.clone(),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+// Last wave's board is gone, so none of its edits can be taken back.
+pub fn clear_placement_history(
+    mut history: ResMut<PlacementHistory>,
+    mut held: ResMut<HeldSummon>,
+) {
+    history.clear();
+    held.0 = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_keep_mana_in_step() {
+        let summon: SummonType = ron::from_str(
+            r#"SummonType (
+                summon_name: "Skeleton",
+                sprite_idx: 2,
+                mana_cost: 1,
+                health: 10,
+                stamina: 10,
+                stamina_regen: 5,
+                attacks: [],
+                movements: [],
+                brain: "fighter",
+                short_code: "N",
+                prerequisites: (0, None),
+            )"#,
+        )
+        .unwrap();
+        let mut minions = SummonedMinions::default();
+        let mut history = PlacementHistory::default();
+        assert!(history.edit(PlacementEdit::Add(summon.clone(), (1, 1)), &mut minions));
+        assert!(history.edit(PlacementEdit::Add(summon.clone(), (2, 2)), &mut minions));
+        assert!(!history.edit(PlacementEdit::Add(summon.clone(), (2, 2)), &mut minions));
+        assert!(history.edit(PlacementEdit::Move((1, 1), (3, 3)), &mut minions));
+        assert!(history.edit(PlacementEdit::Remove(summon.clone(), (2, 2)), &mut minions));
+        assert_eq!(minions.summons(), 1);
+
+        for _ in 0..4 {
+            history.undo(&mut minions).unwrap();
+        }
+        assert!(history.undo(&mut minions).is_none());
+        assert_eq!((minions.summons(), minions.mana()), (0, 0));
+
+        assert_eq!(
+            history.redo(&mut minions),
+            Some(PlacementEdit::Add(summon.clone(), (1, 1)))
+        );
+        history.redo(&mut minions).unwrap();
+        assert_eq!(
+            history.redo(&mut minions),
+            Some(PlacementEdit::Move((1, 1), (3, 3)))
+        );
+        assert!(minions.has_spawn_location(3, 3));
+        assert_eq!(minions.mana(), summon.mana_cost() * 2);
+
+        // A new edit means there's nothing left to redo.
+        assert!(history.edit(PlacementEdit::Move((3, 3), (4, 4)), &mut minions));
+        assert!(history.redo(&mut minions).is_none());
+        // The moved summon refunds its mana from its new tile.
+        assert!(history.edit(PlacementEdit::Remove(summon.clone(), (4, 4)), &mut minions));
+        assert_eq!(minions.mana(), summon.mana_cost());
+    }
+}
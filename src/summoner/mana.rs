@@ -23,6 +23,7 @@ impl Mana {
 
 pub fn mana_bar_system(
     mana: Res<Mana>,
+    localization: Res<Localization>,
     mut fill_query: Query<(&ManaFill, &mut Style)>,
     mut text_query: Query<(&ManaText, &mut Text)>,
 ) {
@@ -30,7 +31,10 @@ pub fn mana_bar_system(
         style.width = Val::Percent((mana.used_mana as f32 / mana.max_mana as f32) * 100.);
     }
     for (_, mut text) in text_query.iter_mut() {
-        text.sections[0].value = format!("Mana available: {}/{}", mana.mana_left(), mana.max_mana);
+        text.sections[0].value = localization.format(
+            "mana.available",
+            &[("left", &mana.mana_left()), ("max", &mana.max_mana)],
+        );
     }
 }
 
@@ -1,13 +1,23 @@
 use crate::prelude::*;
 
 mod book;
+mod formations;
+mod fusion;
 mod help;
+mod history;
 mod mana;
 mod placement;
+mod synergy;
+mod veterans;
 pub use book::*;
+pub use formations::*;
+pub use fusion::*;
 pub use help::*;
+pub use history::*;
 pub use mana::*;
 pub use placement::*;
+pub use synergy::*;
+pub use veterans::*;
 
 pub struct SummonerPlugin;
 
@@ -15,15 +25,39 @@ impl Plugin for SummonerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Mana>()
             .init_resource::<KnownSummons>()
+            .init_resource::<Veterans>()
+            .init_resource::<Synergies>()
+            .init_resource::<ArmySynergies>()
+            .add_systems(
+                OnExit(GameState::Loading),
+                (load_synergies, validate_fusions_on_load),
+            )
+            .add_systems(OnEnter(GameState::Battling), count_army_synergies)
             // Place the summons on the board
             .init_resource::<SummonedMinions>()
             .init_resource::<EnemyMinions>()
+            .init_resource::<PlacementHistory>()
+            .init_resource::<HeldSummon>()
+            .init_resource::<PresetNaming>()
+            .insert_resource(Formations::load())
             .add_systems(OnEnter(GameState::Summoning), spawn_help_overlay)
             .add_systems(Update, show_hovered_stats)
             .add_systems(OnExit(GameState::Battling), despawn_help_overlay)
             .add_systems(
                 Update,
-                (place_summon, animate_summons, remove_summon)
+                (
+                    (
+                        place_summon,
+                        remove_summon,
+                        undo_placement,
+                        use_formation_presets,
+                    )
+                        .run_if(not(naming_preset)),
+                    animate_summons,
+                    show_held_summon,
+                    name_formation_preset,
+                    show_formation_name,
+                )
                     .run_if(in_state(GameState::Summoning)),
             )
             .add_systems(
@@ -33,6 +67,7 @@ impl Plugin for SummonerPlugin {
                     mana_tally_system,
                     animate_summoning_scroll_opening,
                     show_hotkeys,
+                    show_synergy_progress,
                     handle_summon_button_interactions.after(place_summon),
                 )
                     .run_if(in_state(GameState::Summoning)),
@@ -43,8 +78,15 @@ impl Plugin for SummonerPlugin {
             )
             .add_systems(
                 OnEnter(GameState::Summoning),
-                (spawn_summoning_scroll, clear_summons),
-            );
+                (
+                    spawn_summoning_scroll,
+                    clear_summons,
+                    clear_placement_history,
+                    forget_preset_name,
+                    restore_last_formation.after(clear_summons),
+                ),
+            )
+            .add_systems(OnExit(GameState::Summoning), remember_formation);
     }
 }
 
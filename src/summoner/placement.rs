@@ -1,6 +1,8 @@
-use crate::prelude::*;
+use crate::{prelude::*, summons::PREVIEW_TINT};
 
-use super::{mana, HOTKEYS};
+use super::{
+    control_held, mana, show_placement_edit, PlacementEdit, PlacementHistory, Veterans, HOTKEYS,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, Resource, Default, Asset, TypePath)]
 pub struct SummonedMinions {
@@ -9,6 +11,26 @@ pub struct SummonedMinions {
     mana: i32,
     #[serde(skip)]
     mana_locations: HashMap<(usize, usize), i32>,
+    // Experience the army's summon types had earned, so shared armies keep their veterans.
+    #[serde(default)]
+    veterans: Veterans,
+}
+
+// SummonedMinions as rune codes stored them before veterans.
+#[derive(Deserialize)]
+pub struct UnrankedMinions {
+    spawn_locations: HashMap<(usize, usize), String>,
+    mana: i32,
+}
+
+impl From<UnrankedMinions> for SummonedMinions {
+    fn from(minions: UnrankedMinions) -> Self {
+        Self {
+            spawn_locations: minions.spawn_locations,
+            mana: minions.mana,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -54,8 +76,8 @@ impl SummonedMinions {
         assets_summoned: &Assets<SummonType>,
     ) {
         let mut short_code = HashMap::new();
-        for (_, summon) in summon_assets.player_summons.iter() {
-            let summon_type = assets_summoned.get(&*summon).unwrap();
+        for summon in summon_assets.owned_summons() {
+            let summon_type = assets_summoned.get(summon).unwrap();
             short_code.insert(summon_type.name().to_string(), summon_type.short_code());
         }
         let mut new_spawns = HashMap::new();
@@ -64,14 +86,22 @@ impl SummonedMinions {
                 new_spawns.insert((*x, *y), short_code.to_string());
             }
         }
+        // Only the types in the army, to keep rune codes short.
+        self.veterans = self.veterans.renamed(|summon| {
+            self.spawn_locations
+                .values()
+                .any(|placed| placed == summon)
+                .then(|| short_code.get(summon).map(|code| code.to_string()))
+                .flatten()
+        });
         self.spawn_locations = new_spawns;
         self.mana_locations = HashMap::new();
     }
 
     pub fn expand(&mut self, summon_assets: &SummonsAssets, assets_summoned: &Assets<SummonType>) {
         let mut short_code = HashMap::new();
-        for (_, summon) in summon_assets.player_summons.iter() {
-            let summon_type = assets_summoned.get(&*summon).unwrap();
+        for summon in summon_assets.owned_summons() {
+            let summon_type = assets_summoned.get(summon).unwrap();
             short_code.insert(summon_type.short_code().to_string(), summon_type.name());
         }
         let mut new_spawns = HashMap::new();
@@ -81,6 +111,9 @@ impl SummonedMinions {
             }
         }
         self.spawn_locations = new_spawns;
+        self.veterans = self
+            .veterans
+            .renamed(|code| short_code.get(code).map(|name| name.to_string()));
     }
 
     pub fn has_spawn_location(&self, x: usize, y: usize) -> bool {
@@ -114,6 +147,21 @@ impl SummonedMinions {
         }
     }
 
+    // Moves a summon, and the mana it cost, onto an empty tile.
+    pub fn move_summon(&mut self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if self.spawn_locations.contains_key(&to) {
+            return false;
+        }
+        let Some(summon_name) = self.spawn_locations.remove(&from) else {
+            return false;
+        };
+        self.spawn_locations.insert(to, summon_name);
+        if let Some(mana_cost) = self.mana_locations.remove(&from) {
+            self.mana_locations.insert(to, mana_cost);
+        }
+        true
+    }
+
     pub fn pop_summon(&mut self) -> Option<(usize, usize, String)> {
         let (x, y) = self.spawn_locations.keys().next()?.clone();
         let summon_name = self.spawn_locations.remove(&(x, y))?;
@@ -126,6 +174,14 @@ impl SummonedMinions {
     pub fn mana(&self) -> i32 {
         self.mana
     }
+
+    pub fn veterans(&self) -> &Veterans {
+        &self.veterans
+    }
+
+    pub fn set_veterans(&mut self, veterans: Veterans) {
+        self.veterans = veterans;
+    }
 }
 
 pub fn animate_summons(time: Res<Time>, mut query: Query<(&mut Summon, &mut Transform)>) {
@@ -138,28 +194,38 @@ pub fn animate_summons(time: Res<Time>, mut query: Query<(&mut Summon, &mut Tran
     }
 }
 
+// A placed summon picked up with a click, waiting for a click on an empty tile.
+#[derive(Resource, Default)]
+pub struct HeldSummon(pub Option<(usize, usize)>);
+
 pub fn remove_summon(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     board_mouse_state: Res<BoardMouseState>,
+    textures: Res<TextureAssets>,
     mut summoned_minions: ResMut<SummonedMinions>,
-    summoned_entities: Query<(Entity, &Summon)>,
+    mut history: ResMut<PlacementHistory>,
+    mut held: ResMut<HeldSummon>,
+    mut previews: Query<(Entity, &mut Summon)>,
     sounds: Res<AudioAssets>,
 ) {
     if let Some((x, y)) = board_mouse_state.pickable_tile {
         if keyboard_input.just_pressed(KeyCode::Delete)
             || keyboard_input.just_pressed(KeyCode::Backspace)
         {
-            if summoned_minions.remove_summon(x, y) {
-                commands.spawn(AudioBundle {
-                    source: sounds.remove.clone(),
-                    ..Default::default()
-                });
-                for (entity, summon) in summoned_entities.iter() {
-                    if summon.x == x && summon.y == y {
-                        commands.entity(entity).despawn_recursive();
-                    };
+            let Some(summon) = previews
+                .iter()
+                .find(|(_, summon)| summon.x == x && summon.y == y)
+                .map(|(_, summon)| summon.summon_type.clone())
+            else {
+                return;
+            };
+            let edit = PlacementEdit::Remove(summon, (x, y));
+            if history.edit(edit.clone(), &mut summoned_minions) {
+                if held.0 == Some((x, y)) {
+                    held.0 = None;
                 }
+                show_placement_edit(&edit, &mut commands, &textures, &sounds, &mut previews);
             }
         }
     }
@@ -173,13 +239,31 @@ pub fn place_summon(
     board_mouse_state: Res<BoardMouseState>,
     known_summons: Res<KnownSummons>,
     mut summoned: ResMut<SummonedMinions>,
+    mut history: ResMut<PlacementHistory>,
+    mut held: ResMut<HeldSummon>,
+    mut previews: Query<(Entity, &mut Summon)>,
     mana: Res<mana::Mana>,
     sounds: Res<AudioAssets>,
 ) {
-    if let Some((x, y)) = board_mouse_state.pickable_tile {
-        if summoned.has_spawn_location(x, y) {
+    let Some((x, y)) = board_mouse_state.pickable_tile else {
+        return;
+    };
+    let mut edit = None;
+    if button_input.just_pressed(MouseButton::Left) {
+        if let Some(from) = held.0.take() {
+            // Clicking the held summon again puts it back down where it was.
+            if !summoned.has_spawn_location(x, y) {
+                edit = Some(PlacementEdit::Move(from, (x, y)));
+            }
+        } else if summoned.has_spawn_location(x, y) {
+            held.0 = Some((x, y));
             return;
+        } else if let Some(summon) = known_summons.get_active() {
+            if mana.mana_left() >= summon.mana_cost() {
+                edit = Some(PlacementEdit::Add(summon, (x, y)));
+            }
         }
+    } else if !summoned.has_spawn_location(x, y) && !control_held(&keyboard_input) {
         for (i, key) in HOTKEYS.iter().enumerate() {
             if i >= known_summons.length() as usize {
                 break;
@@ -188,27 +272,39 @@ pub fn place_summon(
                 if known_summons.get_active() == known_summons.get_by_hotkey(*key) {
                     let summon = known_summons.get_active().unwrap();
                     if mana.mana_left() >= summon.mana_cost() {
-                        spawn_summon(&mut commands, &textures, summon.clone(), x, y, false);
-                        commands.spawn(AudioBundle {
-                            source: sounds.place.clone(),
-                            ..Default::default()
-                        });
-                        summoned.add_summon(summon, x, y);
+                        edit = Some(PlacementEdit::Add(summon, (x, y)));
                     }
                 }
             }
         }
-        if button_input.just_pressed(MouseButton::Left) {
-            if let Some(summon) = known_summons.get_active() {
-                if mana.mana_left() >= summon.mana_cost() {
-                    spawn_summon(&mut commands, &textures, summon.clone(), x, y, false);
-                    commands.spawn(AudioBundle {
-                        source: sounds.place.clone(),
-                        ..Default::default()
-                    });
-                    summoned.add_summon(summon, x, y);
-                }
-            }
+    }
+    if let Some(edit) = edit {
+        if history.edit(edit.clone(), &mut summoned) {
+            show_placement_edit(&edit, &mut commands, &textures, &sounds, &mut previews);
+        }
+    }
+}
+
+// Tints the held summon so it's clear which one the next click moves. Only the sprites
+// picked up or put down are touched, and by entity since a move changes the tile.
+pub fn show_held_summon(
+    held: Res<HeldSummon>,
+    mut tinted: Local<Option<Entity>>,
+    mut previews: Query<(Entity, &Summon, &mut Sprite)>,
+) {
+    if !held.is_changed() {
+        return;
+    }
+    if let Some(entity) = tinted.take() {
+        if let Ok((_, _, mut sprite)) = previews.get_mut(entity) {
+            sprite.color = PREVIEW_TINT;
         }
     }
+    if let Some((entity, _, mut sprite)) = previews
+        .iter_mut()
+        .find(|(_, summon, _)| held.0 == Some((summon.x, summon.y)))
+    {
+        sprite.color = Color::rgb(1., 1., 0.6);
+        *tinted = Some(entity);
+    }
 }
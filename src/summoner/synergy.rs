@@ -0,0 +1,248 @@
+use crate::{prelude::*, summons::Tribe};
+
+pub const SYNERGIES_FILE: &str = "tribes.synergies";
+
+// Bonuses for fielding several units of one tribe, from tribes.synergies.
+#[derive(Serialize, Deserialize, Resource, Asset, TypePath, Clone, Debug, Default)]
+pub struct Synergies(pub Vec<TribeSynergy>);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TribeSynergy {
+    pub tribe: Tribe,
+    // In ascending order of units. Every tier reached applies, not just the highest.
+    pub tiers: Vec<SynergyTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SynergyTier {
+    pub units: usize,
+    pub bonus: AuraEffect,
+}
+
+impl Synergies {
+    // Bonuses each tribe in the army has earned.
+    pub fn bonuses(&self, counts: &HashMap<Tribe, usize>) -> Vec<(Tribe, AuraEffect)> {
+        let mut bonuses = vec![];
+        for synergy in &self.0 {
+            let units = counts.get(&synergy.tribe).copied().unwrap_or(0);
+            for tier in synergy.tiers.iter().take_while(|tier| units >= tier.units) {
+                bonuses.push((synergy.tribe.clone(), tier.bonus.clone()));
+            }
+        }
+        bonuses
+    }
+
+    // One line per tribe in the army, like "Undead 3/4: Horde: +Health".
+    pub fn progress(
+        &self,
+        counts: &HashMap<Tribe, usize>,
+        localization: &Localization,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+        for synergy in &self.0 {
+            let units = counts.get(&synergy.tribe).copied().unwrap_or(0);
+            if units == 0 {
+                continue;
+            }
+            let active: Vec<_> = synergy
+                .tiers
+                .iter()
+                .take_while(|tier| units >= tier.units)
+                .map(|tier| tier.bonus.tagline(localization))
+                .collect();
+            let tribe = synergy.tribe.name(localization);
+            let progress = match synergy.tiers.get(active.len()) {
+                Some(next) => localization.format(
+                    "synergy.progress",
+                    &[("tribe", &tribe), ("units", &units), ("next", &next.units)],
+                ),
+                None => localization.format("synergy.max", &[("tribe", &tribe), ("units", &units)]),
+            };
+            lines.push(match active.is_empty() {
+                true => progress,
+                false => localization.format(
+                    "synergy.bonuses",
+                    &[("progress", &progress), ("bonuses", &active.join(", "))],
+                ),
+            });
+        }
+        lines
+    }
+}
+
+pub fn tribe_counts(tribes: impl Iterator<Item = Tribe>) -> HashMap<Tribe, usize> {
+    let mut counts = HashMap::new();
+    for tribe in tribes {
+        *counts.entry(tribe).or_default() += 1;
+    }
+    counts
+}
+
+// The synergies each side earned, fixed when the battle starts since units leave the
+// armies as they're spawned.
+#[derive(Resource, Default)]
+pub struct ArmySynergies {
+    pub player: Vec<(Tribe, AuraEffect)>,
+    pub enemy: Vec<(Tribe, AuraEffect)>,
+}
+
+impl ArmySynergies {
+    pub fn bonuses<'a>(
+        &'a self,
+        faction: &Faction,
+        tribe: &'a Tribe,
+    ) -> impl Iterator<Item = &'a AuraEffect> {
+        let bonuses = match faction {
+            Faction::Player => &self.player,
+            Faction::Enemy => &self.enemy,
+        };
+        bonuses
+            .iter()
+            .filter(move |(bonus_tribe, _)| bonus_tribe == tribe)
+            .map(|(_, bonus)| bonus)
+    }
+}
+
+pub fn load_synergies(
+    mut synergies: ResMut<Synergies>,
+    summon_assets: Res<SummonsAssets>,
+    synergy_assets: Res<Assets<Synergies>>,
+) {
+    match synergy_assets.get(summon_assets.synergies.clone()) {
+        Some(loaded) => *synergies = loaded.clone(),
+        None => warn!("{} did not load, so no tribe has synergies", SYNERGIES_FILE),
+    }
+}
+
+pub fn count_army_synergies(
+    synergies: Res<Synergies>,
+    known_summons: Res<KnownSummons>,
+    my_minions: Res<SummonedMinions>,
+    enemy_minions: Res<EnemyMinions>,
+    summons: Res<SummonsAssets>,
+    summon_types: Res<Assets<SummonType>>,
+    mut army_synergies: ResMut<ArmySynergies>,
+) {
+    let player = tribe_counts(
+        my_minions
+            .iter()
+            .filter(|(_, summon)| known_summons.has(summon))
+            .map(|(_, summon)| known_summons.get(summon).tribe),
+    );
+    let enemy = tribe_counts(enemy_minions.0.iter().filter_map(|(_, summon)| {
+        summons
+            .summon_handle(summon)
+            .and_then(|handle| summon_types.get(handle))
+            .map(|summon_type| summon_type.tribe.clone())
+    }));
+    *army_synergies = ArmySynergies {
+        player: synergies.bonuses(&player),
+        enemy: synergies.bonuses(&enemy),
+    };
+}
+
+#[derive(Component)]
+pub struct SynergyText;
+
+pub fn spawn_synergy_text(parent: &mut ChildBuilder) -> Entity {
+    parent
+        .spawn((
+            TextBundle {
+                text: Text::from_sections(vec![TextSection {
+                    value: "".to_string(),
+                    style: TextStyle {
+                        font: Default::default(),
+                        font_size: 14.0,
+                        color: Color::WHITE,
+                    },
+                }]),
+                ..Default::default()
+            },
+            Class::new("summon_scroll__synergies"),
+            SynergyText,
+        ))
+        .id()
+}
+
+pub fn show_synergy_progress(
+    synergies: Res<Synergies>,
+    known_summons: Res<KnownSummons>,
+    my_minions: Res<SummonedMinions>,
+    localization: Res<Localization>,
+    mut text_query: Query<&mut Text, With<SynergyText>>,
+) {
+    let counts = tribe_counts(
+        my_minions
+            .iter()
+            .filter(|(_, summon)| known_summons.has(summon))
+            .map(|(_, summon)| known_summons.get(summon).tribe),
+    );
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = synergies.progress(&counts, &localization).join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiers_stack_as_units_are_added() {
+        let tier = |units, name: &str, amount| SynergyTier {
+            units,
+            bonus: AuraEffect::Health(name.to_string(), amount, vec![]),
+        };
+        let synergies = Synergies(vec![
+            TribeSynergy {
+                tribe: Tribe::Angel,
+                tiers: vec![tier(2, "Host", 1)],
+            },
+            TribeSynergy {
+                tribe: Tribe::Undead,
+                tiers: vec![
+                    tier(2, "Horde", 1),
+                    tier(4, "Legion", 2),
+                    tier(6, "Dead", 3),
+                ],
+            },
+        ]);
+        let counts = tribe_counts(
+            [
+                Tribe::Undead,
+                Tribe::Undead,
+                Tribe::Undead,
+                Tribe::Undead,
+                Tribe::Angel,
+                Tribe::Enemy,
+            ]
+            .into_iter(),
+        );
+        let bonuses: Vec<_> = synergies
+            .bonuses(&counts)
+            .into_iter()
+            .map(|(tribe, bonus)| (tribe, bonus.name().to_string()))
+            .collect();
+        assert_eq!(
+            bonuses,
+            vec![
+                (Tribe::Undead, "Horde".to_string()),
+                (Tribe::Undead, "Legion".to_string()),
+            ]
+        );
+        let localization = Localization::english(&[
+            ("tribe.angel", "Angel"),
+            ("tribe.undead", "Undead"),
+            ("synergy.progress", "{tribe} {units}/{next}"),
+            ("synergy.bonuses", "{progress}: {bonuses}"),
+            ("aura.tagline", "{name}: {sign}{stat}"),
+            ("stat.health", "Health"),
+        ]);
+        assert_eq!(
+            synergies.progress(&counts, &localization),
+            vec![
+                "Angel 1/2".to_string(),
+                "Undead 4/6: Horde: +Health, Legion: +Health".to_string(),
+            ]
+        );
+    }
+}
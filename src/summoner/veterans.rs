@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+pub const KILL_EXPERIENCE: i32 = 2;
+pub const SURVIVAL_EXPERIENCE: i32 = 1;
+
+// Experience each summon type has earned this run, by summon name. Units are rebuilt every
+// wave, so veterancy belongs to the type rather than to one unit.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Veterans(HashMap<String, i32>);
+
+impl Veterans {
+    pub fn experience(&self, summon: &str) -> i32 {
+        self.0.get(summon).copied().unwrap_or(0)
+    }
+
+    pub fn gain(&mut self, summon: &str, experience: i32) {
+        *self.0.entry(summon.to_string()).or_default() += experience;
+    }
+
+    // Keeps the summons `rename` knows, under their new names.
+    pub fn renamed(&self, rename: impl Fn(&str) -> Option<String>) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter_map(|(summon, experience)| Some((rename(summon)?, *experience)))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_stack_bonuses_from_experience() {
+        let summon: SummonType = ron::from_str(
+            r#"SummonType (
+                summon_name: "Recruit",
+                sprite_idx: 0,
+                mana_cost: 1,
+                health: 5,
+                stamina: 2,
+                stamina_regen: 1,
+                attacks: [(damage: 2, range: 1, stamina_cost: 1)],
+                movements: [],
+                brain: "fighter",
+                short_code: "R",
+                prerequisites: (0, None),
+                levels: [
+                    (experience: 2, health: 2),
+                    (experience: 5, damage: 1, range: 1),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let localization = Localization::english(&[
+            ("veteran.level", "Level {level} - {experience}/{next} XP"),
+            ("veteran.max_level", "Level {level} (Max) - {experience} XP"),
+        ]);
+        let mut veterans = Veterans::default();
+        veterans.gain("Recruit", SURVIVAL_EXPERIENCE);
+        assert_eq!(summon.level(veterans.experience("Recruit")), 0);
+        veterans.gain("Recruit", KILL_EXPERIENCE * 2);
+        let stats = summon.veteran_stats(veterans.experience("Recruit"));
+        assert_eq!(summon.level(stats.experience), 2);
+        assert_eq!((stats.health, stats.max_health), (7, 7));
+        assert_eq!((stats.attacks[0].damage, stats.attacks[0].range), (3, 2));
+        assert_eq!(
+            summon.veterancy(stats.experience, &localization),
+            Some("Level 2 (Max) - 5 XP".to_string())
+        );
+        assert_eq!(
+            summon.veterancy(3, &localization),
+            Some("Level 1 - 3/5 XP".to_string())
+        );
+    }
+}
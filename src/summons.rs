@@ -1,6 +1,6 @@
 use crate::{battle::AuraEffect, prelude::*};
 
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Tribe {
     Angel,
     Undead,
@@ -13,19 +13,37 @@ pub enum Tribe {
 }
 
 impl Tribe {
-    pub fn tagline(&self) -> &str {
+    // The tribe's locale keys are "tribe.<key>" and "tribe.<key>.tagline".
+    fn key(&self) -> &str {
         match self {
-            Tribe::Angel => "Angel - Powerful warriors with divine abilities.",
-            Tribe::Undead => "Undead - Reanimated corpses with terrifying strength",
-            Tribe::Fairy => "Fairy - Forces of nature and balance",
-            Tribe::Construct => "Construct - Support Angel and Undead with auras",
-            Tribe::Elemental => "Elemental - Support Angel and Fairy with boons upon dying",
-            Tribe::Demon => "Demon - Support Undead and Fairy with powerful pacts",
-            Tribe::Enemy => "Mysterious",
+            Tribe::Angel => "angel",
+            Tribe::Undead => "undead",
+            Tribe::Fairy => "fairy",
+            Tribe::Construct => "construct",
+            Tribe::Elemental => "elemental",
+            Tribe::Demon => "demon",
+            Tribe::Enemy => "enemy",
         }
     }
 
-    pub fn sting(&self) -> &str {
+    pub fn name(&self, localization: &Localization) -> String {
+        localization.text(&format!("tribe.{}", self.key()))
+    }
+
+    pub fn tagline(&self, localization: &Localization) -> String {
+        localization.text(&format!("tribe.{}.tagline", self.key()))
+    }
+
+    // Tribes only have summon and death stings; hurt falls back to the shared hurt sound.
+    pub fn sound(&self, cue: SoundCue) -> Option<&'static str> {
+        match cue {
+            SoundCue::Summon => Some(self.sting()),
+            SoundCue::Death => Some(self.death_sting()),
+            SoundCue::Attack | SoundCue::Hurt => None,
+        }
+    }
+
+    pub fn sting(&self) -> &'static str {
         match self {
             Tribe::Angel => "angel_summon_sting",
             Tribe::Undead => "undead_summon_sting",
@@ -37,7 +55,7 @@ impl Tribe {
         }
     }
 
-    pub fn death_sting(&self) -> &str {
+    pub fn death_sting(&self) -> &'static str {
         match self {
             Tribe::Angel => "angel_death_sting",
             Tribe::Undead => "undead_death_sting",
@@ -50,6 +68,39 @@ impl Tribe {
     }
 }
 
+// What a summon type gains once it has earned `experience`. Bonuses stack with earlier levels.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LevelBonus {
+    pub experience: i32,
+    #[serde(default)]
+    pub health: i32,
+    #[serde(default)]
+    pub stamina_regen: i32,
+    #[serde(default)]
+    pub damage: i32,
+    #[serde(default)]
+    pub range: i32,
+}
+
+// When a unit makes a sound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundCue {
+    Summon,
+    Attack,
+    Hurt,
+    Death,
+}
+
+// A summon's own sounds, by file stem in sounds/. Cues left out use the tribe's.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct SummonSounds {
+    pub summon: Option<String>,
+    pub attack: Option<String>,
+    pub hurt: Option<String>,
+    pub death: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Asset, TypePath)]
 pub struct SummonType {
     summon_name: String,
@@ -71,6 +122,10 @@ pub struct SummonType {
     death_brain: String,
     short_code: String,
     prerequisites: (i32, Option<String>),
+    #[serde(default)]
+    levels: Vec<LevelBonus>,
+    #[serde(default)]
+    sounds: SummonSounds,
 }
 
 impl SummonType {
@@ -91,6 +146,8 @@ impl SummonType {
             brain: "fighter".to_string(),
             death_brain: "".to_string(),
             prerequisites: (0, None),
+            levels: vec![],
+            sounds: SummonSounds::default(),
         }
     }
 
@@ -98,10 +155,24 @@ impl SummonType {
         &self.summon_name
     }
 
+    // The name players see, which a locale may translate as "summon.<name>".
+    pub fn display_name(&self, localization: &Localization) -> String {
+        display_name(&self.summon_name, localization)
+    }
+
     pub fn short_code(&self) -> &str {
         &self.short_code
     }
 
+    pub fn brain(&self) -> &str {
+        &self.brain
+    }
+
+    // Empty when the summon uses the default death brain.
+    pub fn death_brain(&self) -> &str {
+        &self.death_brain
+    }
+
     pub fn get_brain(&self, brain_assets: &BrainAssets) -> Option<Handle<CharacterBrainDef>> {
         brain_assets.brains.get(&*self.brain.as_str()).cloned()
     }
@@ -113,6 +184,21 @@ impl SummonType {
             .cloned()
     }
 
+    pub fn sounds(&self) -> &SummonSounds {
+        &self.sounds
+    }
+
+    // The sound stem for `cue`, falling back to the tribe's. None when neither has one.
+    pub fn sound(&self, cue: SoundCue) -> Option<&str> {
+        let own = match cue {
+            SoundCue::Summon => &self.sounds.summon,
+            SoundCue::Attack => &self.sounds.attack,
+            SoundCue::Hurt => &self.sounds.hurt,
+            SoundCue::Death => &self.sounds.death,
+        };
+        own.as_deref().or_else(|| self.tribe.sound(cue))
+    }
+
     pub fn sprite_idx(&self) -> usize {
         self.sprite_idx
     }
@@ -125,84 +211,116 @@ impl SummonType {
         self.prerequisites.clone()
     }
 
-    pub fn descriptor(&self) -> Vec<TextSection> {
-        vec![
-            TextSection {
-                value: self.summon_name.clone(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 24.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: "\n".to_string(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: self.tagline.clone(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: "\n".to_string(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: self.tribe.tagline().to_string(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: "\n".to_string(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: format!("Health: {}", self.health),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
-            },
-            TextSection {
-                value: "\n".to_string(),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
+    pub fn levels(&self) -> &[LevelBonus] {
+        &self.levels
+    }
+
+    pub fn level(&self, experience: i32) -> usize {
+        self.levels
+            .iter()
+            .take_while(|level| experience >= level.experience)
+            .count()
+    }
+
+    // The stats a unit of this type fights with once the type has earned `experience`.
+    pub fn veteran_stats(&self, experience: i32) -> CharacterStats {
+        let mut stats: CharacterStats = self.clone().into();
+        stats.experience = experience;
+        for bonus in &self.levels[..self.level(experience)] {
+            stats.max_health += bonus.health;
+            stats.health += bonus.health;
+            stats.stamina_regen += bonus.stamina_regen;
+            for attack in stats.attacks.iter_mut() {
+                attack.damage += bonus.damage;
+                attack.range += bonus.range;
+            }
+        }
+        stats
+    }
+
+    // None for summons that never level up.
+    pub fn veterancy(&self, experience: i32, localization: &Localization) -> Option<String> {
+        if self.levels.is_empty() {
+            return None;
+        }
+        let level = self.level(experience);
+        Some(match self.levels.get(level) {
+            Some(next) => localization.format(
+                "veteran.level",
+                &[
+                    ("level", &level),
+                    ("experience", &experience),
+                    ("next", &next.experience),
+                ],
+            ),
+            None => localization.format(
+                "veteran.max_level",
+                &[("level", &level), ("experience", &experience)],
+            ),
+        })
+    }
+
+    // None when anyone can find the summon as loot.
+    pub fn requirement(&self, localization: &Localization) -> Option<String> {
+        let (mana, summon) = &self.prerequisites;
+        let summon = summon
+            .as_ref()
+            .map(|summon| display_name(summon, localization));
+        match (mana, summon) {
+            (0, None) => None,
+            (mana, None) => Some(localization.format("card.requires_mana", &[("mana", mana)])),
+            (0, Some(summon)) => {
+                Some(localization.format("card.requires_summon", &[("summon", &summon)]))
+            }
+            (mana, Some(summon)) => Some(
+                localization.format("card.requires_both", &[("mana", mana), ("summon", &summon)]),
+            ),
+        }
+    }
+
+    // The full stat card. `stats` is what a unit of this type fights with, and `behavior` is
+    // BrainSummaries::describe for it.
+    pub fn descriptor(
+        &self,
+        stats: &CharacterStats,
+        behavior: &[String],
+        localization: &Localization,
+    ) -> Vec<TextSection> {
+        let mut lines = vec![
+            localization.content(
+                &format!("summon.{}.tagline", self.summon_name),
+                &self.tagline,
+            ),
+            self.tribe.tagline(localization),
+            localization.format("card.mana_cost", &[("mana", &self.mana_cost)]),
+        ];
+        lines.extend(self.requirement(localization));
+        lines.extend(stats.stat_lines(localization));
+        lines.extend(behavior.iter().map(|line| format!("- {}", line)));
+        let mut sections = vec![TextSection {
+            value: self.display_name(localization),
+            style: TextStyle {
+                font: Default::default(),
+                font_size: 24.0,
+                color: Color::WHITE,
             },
-            TextSection {
-                value: format!("Mana Cost: {}", self.mana_cost),
-                style: TextStyle {
-                    font: Default::default(),
-                    font_size: 12.0,
-                    color: Color::WHITE,
-                },
+        }];
+        sections.extend(lines.into_iter().map(|line| TextSection {
+            value: format!("\n{}", line),
+            style: TextStyle {
+                font: Default::default(),
+                font_size: 12.0,
+                color: Color::WHITE,
             },
-        ]
+        }));
+        sections
     }
 }
 
+fn display_name(summon: &str, localization: &Localization) -> String {
+    localization.content(&format!("summon.{}", summon), summon)
+}
+
 impl Into<CharacterStats> for SummonType {
     fn into(self) -> CharacterStats {
         CharacterStats {
@@ -217,6 +335,7 @@ impl Into<CharacterStats> for SummonType {
             tribe: self.tribe,
             auras: self.auras,
             applied_auras: vec![],
+            experience: 0,
         }
     }
 }
@@ -232,6 +351,9 @@ pub struct Summon {
 #[derive(Component)]
 pub struct OverheadText(pub f32);
 
+// Summons placed on the board but not yet fighting are tinted blue.
+pub const PREVIEW_TINT: Color = Color::rgb(0.8, 0.8, 1.);
+
 pub fn spawn_summon(
     commands: &mut Commands,
     textures: &Res<TextureAssets>,
@@ -252,7 +374,7 @@ pub fn spawn_summon(
                     color: if real {
                         Color::rgb(1., 1., 1.)
                     } else {
-                        Color::rgb(0.8, 0.8, 1.)
+                        PREVIEW_TINT
                     },
                     ..Default::default()
                 },
@@ -288,3 +410,28 @@ pub fn spawn_summon(
         })
         .id()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summon_sounds_fall_back_to_the_tribe() {
+        let inferno = SummonType {
+            summon_name: "Inferno".to_string(),
+            tribe: Tribe::Elemental,
+            sounds: SummonSounds {
+                summon: Some("demon_summon_sting".to_string()),
+                ..Default::default()
+            },
+            ..SummonType::debug()
+        };
+        assert_eq!(inferno.sound(SoundCue::Summon), Some("demon_summon_sting"));
+        assert_eq!(
+            inferno.sound(SoundCue::Death),
+            Some("elemental_death_sting")
+        );
+        assert_eq!(inferno.sound(SoundCue::Attack), None);
+        assert_eq!(inferno.sound(SoundCue::Hurt), None);
+    }
+}